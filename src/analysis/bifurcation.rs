@@ -0,0 +1,311 @@
+// src/analysis/bifurcation.rs
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use crate::engines::plant_engine::PlantEngine;
+use crate::plants::tropisms::{Plant, Tropisms};
+use crate::simulation::batch::SweepAxis;
+use crate::simulation::parameters::ParameterError;
+use crate::simulation::simulation_env::SimulationEnv;
+
+/// Long-term behaviour of the tracked plant at one parameter value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regime {
+    Equilibrium, // Settled to a fixed value
+    Growth,      // Drifting steadily in one direction
+    Oscillation, // Repeated reversals without net drift
+    Collapse,    // Crossed d_critical into a Pure Time State
+    Extinct,     // Plant died or ran out of energy
+}
+
+impl Regime {
+    pub const ALL: [Regime; 5] = [
+        Regime::Equilibrium,
+        Regime::Growth,
+        Regime::Oscillation,
+        Regime::Collapse,
+        Regime::Extinct,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Regime::Equilibrium => "equilibrium",
+            Regime::Growth => "growth",
+            Regime::Oscillation => "oscillation",
+            Regime::Collapse => "collapse",
+            Regime::Extinct => "extinct",
+        }
+    }
+}
+
+impl fmt::Display for Regime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Plant quantity plotted on the diagram's vertical axis and used to classify the regime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BifurcationVariable {
+    Coherence,
+    Distortion,
+    TemporalComplexity,
+    RhoC,
+    Energy,
+}
+
+impl BifurcationVariable {
+    pub const NAMES: [&'static str; 5] = ["coherence", "distortion", "temporal_complexity", "rho_c", "energy"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "coherence" => Self::Coherence,
+            "distortion" => Self::Distortion,
+            "temporal_complexity" => Self::TemporalComplexity,
+            "rho_c" => Self::RhoC,
+            "energy" => Self::Energy,
+            _ => return None,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Coherence => "coherence",
+            Self::Distortion => "distortion",
+            Self::TemporalComplexity => "temporal_complexity",
+            Self::RhoC => "rho_c",
+            Self::Energy => "energy",
+        }
+    }
+}
+
+/// One recorded state of the tracked plant after the transient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttractorSample {
+    pub coherence: f32,
+    pub distortion: f32,
+    pub temporal_complexity: f32,
+    pub rho_c: f32,
+    pub energy: f32,
+}
+
+impl AttractorSample {
+    pub fn of(plant: &Plant) -> Self {
+        Self {
+            coherence: plant.coherence,
+            distortion: plant.distortion,
+            temporal_complexity: plant.temporal_complexity,
+            rho_c: Tropisms::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy),
+            energy: plant.energy,
+        }
+    }
+
+    pub fn get(&self, variable: BifurcationVariable) -> f32 {
+        match variable {
+            BifurcationVariable::Coherence => self.coherence,
+            BifurcationVariable::Distortion => self.distortion,
+            BifurcationVariable::TemporalComplexity => self.temporal_complexity,
+            BifurcationVariable::RhoC => self.rho_c,
+            BifurcationVariable::Energy => self.energy,
+        }
+    }
+}
+
+/// Attractor samples and regime at one value of the swept parameter
+#[derive(Debug, Clone)]
+pub struct BifurcationPoint {
+    pub value: f32,
+    pub regime: Regime,
+    pub samples: Vec<AttractorSample>,
+}
+
+/// Settings for a one-parameter bifurcation scan
+#[derive(Debug, Clone)]
+pub struct BifurcationConfig {
+    pub parameter: SweepAxis,          // Usually d_critical, alpha or beta; `points` values from min to max
+    pub variable: BifurcationVariable,
+    pub transient_steps: usize,        // Steps discarded before sampling
+    pub sample_steps: usize,           // Steps recorded as attractor samples
+    pub dt: f32,
+    pub seed: u64,
+    pub continuation: bool,            // Start each value from the previous value's final H, D, T
+}
+
+impl BifurcationConfig {
+    pub fn new(parameter: SweepAxis) -> Self {
+        Self {
+            parameter,
+            variable: BifurcationVariable::Distortion,
+            transient_steps: 500,
+            sample_steps: 200,
+            dt: 0.1,
+            seed: 42,
+            continuation: false,
+        }
+    }
+
+    /// Parameter values in scan order
+    pub fn values(&self) -> Vec<f32> {
+        self.parameter.values()
+    }
+}
+
+/// Steps the parameter across its range, running the first plant of `template` to a steady regime at each value
+pub fn bifurcation(config: &BifurcationConfig, template: &SimulationEnv) -> Result<Vec<BifurcationPoint>, ParameterError> {
+    let mut probe = template.clone();
+    for value in config.values() {
+        probe.set_parameter(&config.parameter.name, value)?;
+    }
+    let Some(tracked) = template.plants.first().map(|p| p.id) else { return Ok(Vec::new()) };
+
+    let mut points = Vec::with_capacity(config.parameter.points);
+    let mut carried: Option<Plant> = None;
+    for value in config.values() {
+        let mut env = template.clone();
+        env.reseed(config.seed);
+        env.set_parameter(&config.parameter.name, value)?;
+        if let (Some(previous), Some(plant)) = (&carried, env.plants.iter_mut().find(|p| p.id == tracked)) {
+            plant.coherence = previous.coherence;
+            plant.distortion = previous.distortion;
+            plant.temporal_complexity = previous.temporal_complexity;
+        }
+        let d_critical = env.environment.d_critical;
+        let mut engine = PlantEngine::new(env);
+
+        let mut samples = Vec::with_capacity(config.sample_steps);
+        let mut collapsed = false;
+        let mut alive = true;
+        for step in 0..config.transient_steps + config.sample_steps {
+            engine.update(config.dt);
+            let Some(plant) = engine.env.plants.iter().find(|p| p.id == tracked) else {
+                alive = false;
+                break;
+            };
+            if plant.energy <= 0.0 {
+                alive = false;
+                break;
+            }
+            if step >= config.transient_steps {
+                collapsed |= plant.distortion > d_critical;
+                samples.push(AttractorSample::of(plant));
+            }
+        }
+
+        let regime = if !alive {
+            Regime::Extinct
+        } else if collapsed {
+            Regime::Collapse
+        } else {
+            let values: Vec<f32> = samples.iter().map(|s| s.get(config.variable)).collect();
+            classify(&values)
+        };
+        if config.continuation {
+            carried = engine.env.plants.iter().find(|p| p.id == tracked).cloned();
+        }
+        points.push(BifurcationPoint { value, regime, samples });
+    }
+    Ok(points)
+}
+
+/// Classifies a series of samples from a plant that is alive and below d_critical
+pub fn classify(values: &[f32]) -> Regime {
+    if values.len() < 3 {
+        return Regime::Equilibrium;
+    }
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    if range <= 1e-4 * mean.abs().max(1.0) {
+        return Regime::Equilibrium;
+    }
+
+    // Direction reversals, ignoring steps too small to matter
+    let mut reversals = 0;
+    let mut last_sign = 0.0;
+    for pair in values.windows(2) {
+        let delta = pair[1] - pair[0];
+        if delta.abs() < 1e-3 * range {
+            continue;
+        }
+        let sign = delta.signum();
+        if last_sign != 0.0 && sign != last_sign {
+            reversals += 1;
+        }
+        last_sign = sign;
+    }
+    let drift = (values[values.len() - 1] - values[0]).abs();
+    if reversals >= 2 && drift < 0.5 * range {
+        Regime::Oscillation
+    } else {
+        Regime::Growth
+    }
+}
+
+/// Writes every attractor sample: one row per (parameter value, sample)
+pub fn write_samples_csv(path: &Path, config: &BifurcationConfig, points: &[BifurcationPoint]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(
+        out,
+        "{},regime,sample,coherence,distortion,temporal_complexity,rho_c,energy",
+        config.parameter.name
+    )?;
+    for point in points {
+        for (i, s) in point.samples.iter().enumerate() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{}",
+                point.value, point.regime, i, s.coherence, s.distortion, s.temporal_complexity, s.rho_c, s.energy
+            )?;
+        }
+    }
+    out.flush()
+}
+
+/// Writes one row per parameter value with its regime and the range of the classified variable
+pub fn write_regimes_csv(path: &Path, config: &BifurcationConfig, points: &[BifurcationPoint]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    let variable = config.variable.name();
+    writeln!(out, "{},regime,{}_min,{}_max", config.parameter.name, variable, variable)?;
+    for point in points {
+        let values = point.samples.iter().map(|s| s.get(config.variable));
+        let min = values.clone().fold(f32::NAN, f32::min);
+        let max = values.fold(f32::NAN, f32::max);
+        writeln!(out, "{},{},{},{}", point.value, point.regime, min, max)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(&[1.0; 10]), Regime::Equilibrium);
+        assert_eq!(classify(&[1.0, 2.0, 3.0, 4.0, 5.0]), Regime::Growth);
+        assert_eq!(classify(&[0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0]), Regime::Oscillation);
+    }
+
+    #[test]
+    fn test_low_d_critical_collapses() {
+        let mut config = BifurcationConfig::new(SweepAxis::parse("d_critical=0.001:1000:2").unwrap());
+        config.transient_steps = 20;
+        config.sample_steps = 20;
+        let points = bifurcation(&config, &SimulationEnv::with_seed(2)).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].regime, Regime::Collapse);
+        assert_ne!(points[1].regime, Regime::Collapse);
+        assert_eq!(points[1].samples.len(), 20);
+
+        config.parameter.name = "gamma".to_string();
+        assert!(bifurcation(&config, &SimulationEnv::with_seed(2)).is_err());
+
+        // Only the last value is out of range; it must fail even with no plant to scan
+        let config = BifurcationConfig::new(SweepAxis::parse("metric_coupling=0:1:3").unwrap());
+        let mut empty = SimulationEnv::with_seed(2);
+        empty.plants.clear();
+        assert!(bifurcation(&config, &empty).is_err());
+    }
+}
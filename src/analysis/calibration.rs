@@ -0,0 +1,395 @@
+// src/analysis/calibration.rs
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use nalgebra::Vector3;
+use crate::engines::plant_engine::PlantEngine;
+use crate::simulation::batch::SweepAxis;
+use crate::simulation::parameters::ParameterError;
+use crate::simulation::simulation_env::SimulationEnv;
+
+/// Observed stem-tip positions of one plant over time
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservedTrajectory {
+    pub plant_id: usize,
+    pub samples: Vec<(f32, Vector3<f32>)>, // (time, stem tip), sorted by time
+}
+
+/// Difference between an observation and the model at the same time
+#[derive(Debug, Clone)]
+pub struct Residual {
+    pub plant_id: usize,
+    pub time: f32,
+    pub observed: Vector3<f32>,
+    pub simulated: Option<Vector3<f32>>, // None if the plant no longer exists in the model
+    pub error: f32,
+}
+
+/// Fitted parameter set and how well it reproduces the observations
+#[derive(Debug, Clone)]
+pub struct CalibrationResult {
+    pub parameters: Vec<(String, f32)>,
+    pub sse: f32,
+    pub rmse: f32,
+    pub evaluations: usize,
+    pub residuals: Vec<Residual>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalibrationError {
+    Parameter(ParameterError),
+    InvalidDt(f32),
+    UnknownPlant(usize), // An observed plant_id with no plant in the model
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::Parameter(e) => e.fmt(f),
+            CalibrationError::InvalidDt(dt) => write!(f, "time step {} must be finite and greater than 0", dt),
+            CalibrationError::UnknownPlant(id) => write!(f, "observed plant_id {} has no plant in the model", id),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+impl From<ParameterError> for CalibrationError {
+    fn from(e: ParameterError) -> Self {
+        CalibrationError::Parameter(e)
+    }
+}
+
+/// Settings for fitting model parameters to observed trajectories
+#[derive(Debug, Clone)]
+pub struct CalibrationConfig {
+    pub parameters: Vec<SweepAxis>, // Parameters to fit and their bounds
+    pub dt: f32,
+    pub seed: u64,                  // Every evaluation uses the same noise
+    pub max_evaluations: usize,
+    pub tolerance: f32,             // Stop when the simplex's objective spread falls below this
+    pub missing_penalty: f32,       // Squared error charged when a plant has died in the model
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            parameters: Vec::new(),
+            dt: 0.1,
+            seed: 42,
+            max_evaluations: 400,
+            tolerance: 1e-6,
+            missing_penalty: 100.0,
+        }
+    }
+}
+
+/// Parses trajectory CSV with a header containing `time`, `x`, `y` and optionally `z` and `plant_id`
+pub fn parse_trajectories(text: &str) -> io::Result<Vec<ObservedTrajectory>> {
+    let invalid = |line: usize, msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line, msg));
+    let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'));
+    let (header_line, header) = lines.next().ok_or_else(|| invalid(1, "missing header".to_string()))?;
+    let columns: Vec<String> = header.split(',').map(|c| c.trim().to_lowercase()).collect();
+    let column = |name: &str| columns.iter().position(|c| c == name);
+    let (Some(t_col), Some(x_col), Some(y_col)) = (column("time"), column("x"), column("y")) else {
+        return Err(invalid(header_line + 1, "header must contain time, x and y".to_string()));
+    };
+    let (z_col, id_col) = (column("z"), column("plant_id"));
+
+    let mut trajectories: Vec<ObservedTrajectory> = Vec::new();
+    for (index, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let number = |col: usize| -> io::Result<f32> {
+            let field = fields.get(col).ok_or_else(|| invalid(index + 1, format!("missing column {}", columns[col])))?;
+            field.parse().map_err(|_| invalid(index + 1, format!("invalid number '{}' in column {}", field, columns[col])))
+        };
+        let time = number(t_col)?;
+        let pos = Vector3::new(number(x_col)?, number(y_col)?, z_col.map(number).transpose()?.unwrap_or(0.0));
+        let plant_id = id_col.map(number).transpose()?.unwrap_or(0.0) as usize;
+
+        match trajectories.iter_mut().find(|t| t.plant_id == plant_id) {
+            Some(t) => t.samples.push((time, pos)),
+            None => trajectories.push(ObservedTrajectory { plant_id, samples: vec![(time, pos)] }),
+        }
+    }
+    for t in &mut trajectories {
+        t.samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+    Ok(trajectories)
+}
+
+pub fn load_trajectories(path: &Path) -> io::Result<Vec<ObservedTrajectory>> {
+    parse_trajectories(&fs::read_to_string(path)?)
+}
+
+/// Moves each observed plant so its stem tip starts at its first observation
+pub fn place_plants(env: &mut SimulationEnv, observed: &[ObservedTrajectory]) -> Result<(), CalibrationError> {
+    for trajectory in observed {
+        let plant = env
+            .plants
+            .iter_mut()
+            .find(|p| p.id == trajectory.plant_id)
+            .ok_or(CalibrationError::UnknownPlant(trajectory.plant_id))?;
+        if let Some((_, tip)) = trajectory.samples.first() {
+            plant.pos = tip - plant.stem_dir;
+            plant.path.clear();
+        }
+    }
+    Ok(())
+}
+
+/// Checks the time step and that every observed plant exists in `template`
+fn check_setup(config: &CalibrationConfig, template: &SimulationEnv, observed: &[ObservedTrajectory]) -> Result<(), CalibrationError> {
+    if !(config.dt.is_finite() && config.dt > 0.0) {
+        return Err(CalibrationError::InvalidDt(config.dt));
+    }
+    match observed.iter().find(|t| !template.plants.iter().any(|p| p.id == t.plant_id)) {
+        Some(missing) => Err(CalibrationError::UnknownPlant(missing.plant_id)),
+        None => Ok(()),
+    }
+}
+
+/// Runs the model with `values` for the configured parameters and compares stem tips at every observation
+/// time. Plants that die during the run are charged `missing_penalty`.
+pub fn simulate_residuals(
+    config: &CalibrationConfig,
+    template: &SimulationEnv,
+    observed: &[ObservedTrajectory],
+    values: &[f32],
+) -> Result<Vec<Residual>, CalibrationError> {
+    check_setup(config, template, observed)?;
+    let mut env = template.clone();
+    env.reseed(config.seed);
+    for (axis, value) in config.parameters.iter().zip(values) {
+        env.set_parameter(&axis.name, *value)?;
+    }
+    let mut engine = PlantEngine::new(env);
+
+    // Visit observations in time order, stepping the model forward to each one
+    let mut pending: Vec<(f32, usize, Vector3<f32>)> = observed
+        .iter()
+        .flat_map(|t| t.samples.iter().map(move |(time, pos)| (*time, t.plant_id, *pos)))
+        .collect();
+    pending.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut residuals = Vec::with_capacity(pending.len());
+    for (time, plant_id, observed) in pending {
+        while engine.env.time + 0.5 * config.dt < time {
+            engine.update(config.dt);
+        }
+        let simulated = engine
+            .env
+            .plants
+            .iter()
+            .find(|p| p.id == plant_id)
+            .map(|p| p.pos + p.stem_dir);
+        let error = match simulated {
+            Some(tip) => (tip - observed).norm(),
+            None => config.missing_penalty.sqrt(),
+        };
+        residuals.push(Residual { plant_id, time, observed, simulated, error });
+    }
+    Ok(residuals)
+}
+
+/// Fits the configured parameters by minimising the sum of squared stem-tip errors with Nelder–Mead.
+/// The search starts from the template's current values (clamped into bounds). Fails before searching
+/// if the setup is invalid, and with the model's error if any evaluation fails.
+pub fn calibrate(
+    config: &CalibrationConfig,
+    template: &SimulationEnv,
+    observed: &[ObservedTrajectory],
+) -> Result<CalibrationResult, CalibrationError> {
+    check_setup(config, template, observed)?;
+    let bounds: Vec<(f32, f32)> = config.parameters.iter().map(|p| (p.min.min(p.max), p.max.max(p.min))).collect();
+    let mut start = Vec::with_capacity(bounds.len());
+    for (axis, (lo, hi)) in config.parameters.iter().zip(&bounds) {
+        let current = template
+            .parameter(&axis.name)
+            .ok_or_else(|| ParameterError::Unknown(axis.name.clone()))?;
+        start.push(current.clamp(*lo, *hi));
+    }
+    let clamp = |x: &[f32]| -> Vec<f32> { x.iter().zip(&bounds).map(|(v, (lo, hi))| v.clamp(*lo, *hi)).collect() };
+    let sse = |residuals: &[Residual]| residuals.iter().map(|r| r.error * r.error).sum::<f32>();
+
+    let mut evaluations = 0;
+    let mut failure = None;
+    let mut objective = |x: &[f32]| -> f32 {
+        evaluations += 1;
+        match simulate_residuals(config, template, observed, &clamp(x)) {
+            Ok(residuals) => sse(&residuals),
+            Err(e) => {
+                failure.get_or_insert(e);
+                f32::INFINITY
+            }
+        }
+    };
+    let steps: Vec<f32> = bounds.iter().map(|(lo, hi)| 0.1 * (hi - lo).max(1e-6)).collect();
+    let best = nelder_mead(&mut objective, &start, &steps, config.max_evaluations, config.tolerance);
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
+    let fitted = clamp(&best);
+    let residuals = simulate_residuals(config, template, observed, &fitted)?;
+    let total = sse(&residuals);
+    Ok(CalibrationResult {
+        parameters: config.parameters.iter().map(|p| p.name.clone()).zip(fitted).collect(),
+        sse: total,
+        rmse: (total / residuals.len().max(1) as f32).sqrt(),
+        evaluations,
+        residuals,
+    })
+}
+
+/// Minimises `f` with the Nelder–Mead simplex method, starting from `x0` with initial edge lengths `steps`
+pub fn nelder_mead(
+    f: &mut impl FnMut(&[f32]) -> f32,
+    x0: &[f32],
+    steps: &[f32],
+    max_evaluations: usize,
+    tolerance: f32,
+) -> Vec<f32> {
+    let n = x0.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let (alpha, gamma, rho, sigma) = (1.0, 2.0, 0.5, 0.5);
+    let mut simplex: Vec<(Vec<f32>, f32)> = Vec::with_capacity(n + 1);
+    simplex.push((x0.to_vec(), f(x0)));
+    for i in 0..n {
+        let mut x = x0.to_vec();
+        x[i] += steps[i];
+        let fx = f(&x);
+        simplex.push((x, fx));
+    }
+    let mut evaluations = n + 1;
+    let blend = |a: &[f32], b: &[f32], t: f32| -> Vec<f32> { a.iter().zip(b).map(|(a, b)| a + t * (b - a)).collect() };
+
+    while evaluations < max_evaluations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if (simplex[n].1 - simplex[0].1).abs() <= tolerance {
+            break;
+        }
+        let centroid: Vec<f32> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f32>() / n as f32)
+            .collect();
+        let worst = simplex[n].0.clone();
+
+        let reflected = blend(&centroid, &worst, -alpha);
+        let f_reflected = f(&reflected);
+        evaluations += 1;
+        if f_reflected < simplex[0].1 {
+            let expanded = blend(&centroid, &worst, -gamma);
+            let f_expanded = f(&expanded);
+            evaluations += 1;
+            simplex[n] = if f_expanded < f_reflected { (expanded, f_expanded) } else { (reflected, f_reflected) };
+        } else if f_reflected < simplex[n - 1].1 {
+            simplex[n] = (reflected, f_reflected);
+        } else {
+            let contracted = blend(&centroid, &worst, rho);
+            let f_contracted = f(&contracted);
+            evaluations += 1;
+            if f_contracted < simplex[n].1 {
+                simplex[n] = (contracted, f_contracted);
+            } else {
+                // Shrink everything toward the best vertex
+                let best = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    vertex.0 = blend(&best, &vertex.0, sigma);
+                    vertex.1 = f(&vertex.0);
+                    evaluations += 1;
+                }
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0).0
+}
+
+/// Writes the fitted parameters (`parameter,value`) followed by the fit statistics
+pub fn write_parameters_csv(path: &Path, result: &CalibrationResult) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "parameter,value")?;
+    for (name, value) in &result.parameters {
+        writeln!(out, "{},{}", name, value)?;
+    }
+    writeln!(out, "# sse,{}", result.sse)?;
+    writeln!(out, "# rmse,{}", result.rmse)?;
+    writeln!(out, "# evaluations,{}", result.evaluations)?;
+    out.flush()
+}
+
+/// Writes one row per observation with the simulated tip and the error
+pub fn write_residuals_csv(path: &Path, residuals: &[Residual]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "plant_id,time,obs_x,obs_y,obs_z,sim_x,sim_y,sim_z,error")?;
+    for r in residuals {
+        let sim = r.simulated.unwrap_or(Vector3::repeat(f32::NAN));
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            r.plant_id, r.time, r.observed.x, r.observed.y, r.observed.z, sim.x, sim.y, sim.z, r.error
+        )?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trajectories() {
+        let text = "time,x,y\n0.0,0.0,1.0\n1.0,0.1,1.1\n";
+        let trajectories = parse_trajectories(text).unwrap();
+        assert_eq!(trajectories.len(), 1);
+        assert_eq!(trajectories[0].samples[1], (1.0, Vector3::new(0.1, 1.1, 0.0)));
+
+        let err = parse_trajectories("time,x,y\n0.0,abc,1.0\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_recovers_known_parameter() {
+        let config = CalibrationConfig {
+            parameters: vec![SweepAxis::parse("phototropism=0.05:0.3").unwrap()],
+            ..CalibrationConfig::default()
+        };
+        let template = SimulationEnv::with_seed(9);
+
+        // Synthetic observations generated with phototropism = 0.18
+        let times: Vec<(f32, Vector3<f32>)> = (1..=10).map(|i| (i as f32, Vector3::zeros())).collect();
+        let skeleton = vec![ObservedTrajectory { plant_id: 0, samples: times }];
+        let truth = simulate_residuals(&config, &template, &skeleton, &[0.18]).unwrap();
+        let observed = vec![ObservedTrajectory {
+            plant_id: 0,
+            samples: truth.iter().map(|r| (r.time, r.simulated.unwrap())).collect(),
+        }];
+
+        let result = calibrate(&config, &template, &observed).unwrap();
+        assert!((result.parameters[0].1 - 0.18).abs() < 0.01, "{:?}", result.parameters);
+        assert!(result.rmse < 1e-2);
+    }
+
+    #[test]
+    fn test_rejects_bad_setup() {
+        let config = CalibrationConfig {
+            parameters: vec![SweepAxis::parse("phototropism=0.05:0.3").unwrap()],
+            ..CalibrationConfig::default()
+        };
+        let mut template = SimulationEnv::with_seed(9);
+        let observed = vec![ObservedTrajectory { plant_id: 0, samples: vec![(1.0, Vector3::new(0.5, 1.5, 0.0))] }];
+
+        let stalled = CalibrationConfig { dt: 0.0, ..config.clone() };
+        assert_eq!(calibrate(&stalled, &template, &observed).err(), Some(CalibrationError::InvalidDt(0.0)));
+        let stranger = vec![ObservedTrajectory { plant_id: 7, ..observed[0].clone() }];
+        assert_eq!(calibrate(&config, &template, &stranger).err(), Some(CalibrationError::UnknownPlant(7)));
+        assert_eq!(place_plants(&mut template, &stranger), Err(CalibrationError::UnknownPlant(7)));
+
+        place_plants(&mut template, &observed).unwrap();
+        let plant = &template.plants[0];
+        assert!((plant.pos + plant.stem_dir - observed[0].samples[0].1).norm() < 1e-6);
+    }
+}
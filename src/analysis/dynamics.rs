@@ -0,0 +1,264 @@
+// src/analysis/dynamics.rs
+use std::fmt;
+use nalgebra::{Complex, Matrix3, Vector3};
+use crate::plants::tropisms::{DynamicsParams, DynamicsState, Environment, Plant, Tropisms};
+
+/// Tolerance on |λ| - 1 below which an eigenvalue counts as neutral (allows for finite-difference error)
+const NEUTRAL_TOLERANCE: f64 = 1e-4;
+
+/// Linear stability of a fixed point of the dt-map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stability {
+    Stable,   // Every non-neutral eigenvalue has |λ| < 1
+    Unstable, // Every non-neutral eigenvalue has |λ| > 1
+    Saddle,   // Both contracting and expanding directions
+    Neutral,  // All eigenvalues on the unit circle; linearisation is inconclusive
+}
+
+impl fmt::Display for Stability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stability::Stable => "stable",
+            Stability::Unstable => "unstable",
+            Stability::Saddle => "saddle",
+            Stability::Neutral => "neutral",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A fixed point (H, D, T) and its linearisation
+#[derive(Debug, Clone)]
+pub struct FixedPoint {
+    pub state: Vector3<f64>,
+    pub jacobian: Matrix3<f64>,
+    pub eigenvalues: Vec<Complex<f64>>,
+    pub stability: Stability,
+    pub neutral_directions: usize, // Eigenvalues with |λ| = 1, e.g. along T, which never feeds back
+}
+
+/// Largest Lyapunov exponent estimated along one trajectory
+#[derive(Debug, Clone)]
+pub struct LyapunovEstimate {
+    pub exponent: f64,   // Per unit of simulated time
+    pub steps: usize,    // Steps actually averaged over
+    pub collapsed: bool, // Trajectory crossed d_critical (Pure Time State) before the end
+    pub final_state: Vector3<f64>,
+}
+
+/// The H–D–T equations of `Tropisms::dynamics_step` as a discrete map x → F(x) with x = (H, D, T),
+/// evaluated in f64 with the plant's energy and position held fixed
+#[derive(Debug, Clone)]
+pub struct DynamicsAnalysis {
+    pub params: DynamicsParams<f64>,
+    pub dt: f64,
+    pub d_critical: f64,
+}
+
+impl DynamicsAnalysis {
+    pub fn new(params: DynamicsParams<f32>, dt: f32, d_critical: f32) -> Self {
+        Self {
+            params: DynamicsParams {
+                alpha: params.alpha as f64,
+                beta: params.beta as f64,
+                energy: params.energy as f64,
+                grad_s: params.grad_s as f64,
+            },
+            dt: dt as f64,
+            d_critical: d_critical as f64,
+        }
+    }
+
+    /// Analyses the dynamics `plant` currently experiences in `env`
+    pub fn for_plant(plant: &Plant, env: &Environment, dt: f32) -> Self {
+        Self::new(Tropisms::dynamics_params(plant, env), dt, env.d_critical)
+    }
+
+    /// Current (H, D, T) of a plant
+    pub fn state_of(plant: &Plant) -> Vector3<f64> {
+        Vector3::new(plant.coherence as f64, plant.distortion as f64, plant.temporal_complexity as f64)
+    }
+
+    /// One step of the engine's equations
+    pub fn map(&self, x: &Vector3<f64>) -> Vector3<f64> {
+        let state = DynamicsState { coherence: x.x, distortion: x.y, temporal_complexity: x.z };
+        let next = Tropisms::dynamics_step(state, self.params, self.dt);
+        Vector3::new(next.coherence, next.distortion, next.temporal_complexity)
+    }
+
+    /// Jacobian of the map by central differences
+    pub fn jacobian(&self, x: &Vector3<f64>) -> Matrix3<f64> {
+        let mut jacobian = Matrix3::zeros();
+        for j in 0..3 {
+            let h = 1e-6 * x[j].abs().max(1.0);
+            let mut forward = *x;
+            let mut backward = *x;
+            forward[j] += h;
+            backward[j] -= h;
+            jacobian.set_column(j, &((self.map(&forward) - self.map(&backward)) / (2.0 * h)));
+        }
+        jacobian
+    }
+
+    /// Linearises the map at `x` and classifies it by eigenvalue modulus
+    pub fn linearize(&self, x: &Vector3<f64>) -> FixedPoint {
+        let jacobian = self.jacobian(x);
+        let eigenvalues: Vec<Complex<f64>> = jacobian.complex_eigenvalues().iter().copied().collect();
+        let (mut contracting, mut expanding, mut neutral) = (0, 0, 0);
+        for lambda in &eigenvalues {
+            let modulus = lambda.re.hypot(lambda.im);
+            if (modulus - 1.0).abs() <= NEUTRAL_TOLERANCE {
+                neutral += 1;
+            } else if modulus < 1.0 {
+                contracting += 1;
+            } else {
+                expanding += 1;
+            }
+        }
+        let stability = match (contracting > 0, expanding > 0) {
+            (true, true) => Stability::Saddle,
+            (true, false) => Stability::Stable,
+            (false, true) => Stability::Unstable,
+            (false, false) => Stability::Neutral,
+        };
+        FixedPoint { state: *x, jacobian, eigenvalues, stability, neutral_directions: neutral }
+    }
+
+    /// Solves F(x) = x by Gauss–Newton from each guess. The pseudo-inverse copes with the
+    /// singular directions (T never feeds back), so each converged guess lands on the nearest
+    /// point of the fixed set. Points with D outside [0, d_critical] are dropped; duplicates are merged.
+    pub fn fixed_points(&self, guesses: &[Vector3<f64>]) -> Vec<FixedPoint> {
+        let mut found: Vec<FixedPoint> = Vec::new();
+        for guess in guesses {
+            let Some(x) = self.newton(*guess) else { continue };
+            if x.y < 0.0 || x.y > self.d_critical || found.iter().any(|p| (p.state - x).norm() < 1e-6) {
+                continue;
+            }
+            found.push(self.linearize(&x));
+        }
+        found
+    }
+
+    /// Guesses spread over H ∈ [-h_range, h_range], D ∈ [0, d_critical] at T = 0
+    pub fn default_guesses(&self, h_range: f64, points: usize) -> Vec<Vector3<f64>> {
+        let points = points.max(2);
+        let mut guesses = Vec::with_capacity(points * points);
+        for i in 0..points {
+            for j in 0..points {
+                let h = -h_range + 2.0 * h_range * i as f64 / (points - 1) as f64;
+                let d = self.d_critical * j as f64 / (points - 1) as f64;
+                guesses.push(Vector3::new(h, d, 0.0));
+            }
+        }
+        guesses
+    }
+
+    /// Damped Gauss–Newton: each step is halved until the residual shrinks
+    fn newton(&self, mut x: Vector3<f64>) -> Option<Vector3<f64>> {
+        let residual_at = |x: &Vector3<f64>| self.map(x) - x;
+        let mut residual = residual_at(&x);
+        for _ in 0..200 {
+            if !residual.iter().all(|v| v.is_finite()) {
+                return None;
+            }
+            if residual.norm() < 1e-12 * x.norm().max(1.0) {
+                return Some(x);
+            }
+            // Singular values far below the largest belong to neutral directions; ignore them
+            let svd = (self.jacobian(&x) - Matrix3::identity()).svd(true, true);
+            let cutoff = 1e-6 * svd.singular_values.max();
+            let step = svd.pseudo_inverse(cutoff).ok()? * residual;
+            let mut scale = 1.0;
+            loop {
+                let candidate = x - step * scale;
+                let candidate_residual = residual_at(&candidate);
+                if candidate_residual.norm() < residual.norm() {
+                    x = candidate;
+                    residual = candidate_residual;
+                    break;
+                }
+                scale *= 0.5;
+                if scale < 1e-10 {
+                    return None; // Stuck away from a fixed point
+                }
+            }
+        }
+        None
+    }
+
+    /// Benettin estimate of the largest Lyapunov exponent: a tangent vector is pushed through the
+    /// Jacobian and renormalised every step. The first `transient` steps are discarded. Stops early
+    /// if the trajectory crosses d_critical, where the engine collapses the plant.
+    pub fn lyapunov_exponent(&self, start: Vector3<f64>, steps: usize, transient: usize) -> LyapunovEstimate {
+        let mut x = start;
+        let mut tangent = Vector3::new(1.0, 1.0, 1.0).normalize();
+        let mut sum = 0.0;
+        let mut counted = 0;
+        let mut collapsed = false;
+        for step in 0..transient + steps {
+            let next_tangent = self.jacobian(&x) * tangent;
+            x = self.map(&x);
+            if x.y > self.d_critical || !x.iter().all(|v| v.is_finite()) {
+                collapsed = true;
+                break;
+            }
+            let growth = next_tangent.norm();
+            tangent = if growth > 0.0 { next_tangent / growth } else { tangent };
+            if step >= transient && growth > 0.0 {
+                sum += growth.ln();
+                counted += 1;
+            }
+        }
+        LyapunovEstimate {
+            exponent: if counted > 0 { sum / (counted as f64 * self.dt) } else { 0.0 },
+            steps: counted,
+            collapsed,
+            final_state: x,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis() -> DynamicsAnalysis {
+        let params = DynamicsParams { alpha: 0.05, beta: 0.1, energy: 10.0, grad_s: 2.0 };
+        DynamicsAnalysis::new(params, 0.1, 10.0)
+    }
+
+    #[test]
+    fn test_map_matches_engine_step() {
+        let a = analysis();
+        let next = Tropisms::dynamics_step(
+            DynamicsState { coherence: 1.0f32, distortion: 0.5, temporal_complexity: 0.2 },
+            DynamicsParams { alpha: 0.05, beta: 0.1, energy: 10.0, grad_s: 2.0 },
+            0.1,
+        );
+        let x = a.map(&Vector3::new(1.0, 0.5, 0.2));
+        assert!((x.x as f32 - next.coherence).abs() < 1e-6);
+        assert!((x.y as f32 - next.distortion).abs() < 1e-6);
+        assert!((x.z as f32 - next.temporal_complexity).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fixed_points_lie_on_balance_curve() {
+        let a = analysis();
+        let points = a.fixed_points(&a.default_guesses(2.0, 5));
+        assert!(!points.is_empty());
+        for p in &points {
+            // dH/dt = 0 requires D/(H+ε) = -∇S
+            assert!((p.state.y + 2.0 * (p.state.x + 1e-6)).abs() < 1e-6, "{:?}", p.state);
+            // D > 0 needs H < 0, where the coherence direction is repelling
+            assert_eq!(p.stability, Stability::Unstable);
+            assert_eq!(p.neutral_directions, 2);
+        }
+    }
+
+    #[test]
+    fn test_lyapunov_detects_collapse() {
+        let estimate = analysis().lyapunov_exponent(Vector3::new(1.0, 0.0, 0.0), 20000, 10);
+        assert!(estimate.collapsed, "{:?}", estimate);
+        assert!(estimate.exponent.is_finite());
+    }
+}
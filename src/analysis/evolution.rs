@@ -0,0 +1,306 @@
+// src/analysis/evolution.rs
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::engines::plant_engine::PlantEngine;
+use crate::plants::species::Genotype;
+use crate::simulation::headless::{run_headless, RunMetrics};
+use crate::simulation::parameters::ParameterError;
+use crate::simulation::simulation_env::SimulationEnv;
+
+/// Weights combining run metrics into a single fitness score (higher is better)
+#[derive(Debug, Clone)]
+pub struct FitnessWeights {
+    pub rho_c: f32,
+    pub energy: f32,
+    pub light_distance: f32, // Penalty per unit of distance to light
+    pub survival: f32,       // Reward per unit of time survived
+}
+
+impl Default for FitnessWeights {
+    fn default() -> Self {
+        Self {
+            rho_c: 1.0,
+            energy: 0.5,
+            light_distance: 1.0,
+            survival: 0.1,
+        }
+    }
+}
+
+impl FitnessWeights {
+    pub fn score(&self, metrics: &RunMetrics) -> f32 {
+        self.rho_c * metrics.final_rho_c + self.energy * metrics.final_energy
+            - self.light_distance * metrics.light_distance
+            + self.survival * metrics.survival_time
+    }
+}
+
+/// Settings for a genetic-algorithm run
+#[derive(Debug, Clone)]
+pub struct EvolutionConfig {
+    pub genes: Vec<String>,   // Genotype parameters to evolve
+    pub population_size: usize,
+    pub generations: usize,
+    pub steps: usize,         // Simulation steps per evaluation
+    pub dt: f32,
+    pub elitism: usize,       // Best individuals copied unchanged into the next generation
+    pub tournament_size: usize,
+    pub crossover_rate: f32,
+    pub mutation_rate: f32,   // Probability that a gene is mutated
+    pub mutation_scale: f32,  // Relative size of a mutation
+    pub seed: u64,            // Seeds both the GA and every simulation (common random numbers)
+    pub weights: FitnessWeights,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            genes: Genotype::SENSITIVITIES.iter().map(|s| s.to_string()).collect(),
+            population_size: 24,
+            generations: 20,
+            steps: 300,
+            dt: 0.1,
+            elitism: 2,
+            tournament_size: 3,
+            crossover_rate: 0.9,
+            mutation_rate: 0.2,
+            mutation_scale: 0.25,
+            seed: 42,
+            weights: FitnessWeights::default(),
+        }
+    }
+}
+
+impl EvolutionConfig {
+    /// Checks the population size and that every evolved gene is a `Genotype` parameter
+    pub fn validate(&self) -> Result<(), ParameterError> {
+        if self.population_size == 0 {
+            return Err(ParameterError::Invalid { name: "population_size".to_string(), value: 0.0, reason: "must be at least 1" });
+        }
+        match self.genes.iter().find(|gene| !Genotype::PARAMETERS.contains(&gene.as_str())) {
+            Some(unknown) => Err(ParameterError::Unknown(unknown.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A genome and how it scored
+#[derive(Debug, Clone)]
+pub struct Individual {
+    pub genotype: Genotype,
+    pub fitness: f32,
+    pub metrics: RunMetrics,
+}
+
+/// Fitness statistics for one generation
+#[derive(Debug, Clone)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best: f32,
+    pub mean: f32,
+    pub worst: f32,
+    pub std_dev: f32,
+    pub best_genotype: Genotype,
+}
+
+/// Genetic algorithm over plant genotypes, scored by headless `PlantEngine` runs
+pub struct Evolution {
+    pub config: EvolutionConfig,
+    template: SimulationEnv,
+    rng: ChaCha8Rng,
+}
+
+impl Evolution {
+    /// Evolves genotypes for the plants of `template` (usually `SimulationEnv::with_seed`)
+    pub fn new(config: EvolutionConfig, template: SimulationEnv) -> Self {
+        let rng = ChaCha8Rng::seed_from_u64(config.seed);
+        Self { config, template, rng }
+    }
+
+    /// Runs every generation and returns the per-generation history and the best individual found.
+    /// The config is validated before anything runs.
+    pub fn run(&mut self) -> Result<(Vec<GenerationStats>, Individual), ParameterError> {
+        self.run_with(|_| {})
+    }
+
+    /// Like `run`, calling `on_generation` with each generation's stats as soon as it is scored
+    pub fn run_with(
+        &mut self,
+        mut on_generation: impl FnMut(&GenerationStats),
+    ) -> Result<(Vec<GenerationStats>, Individual), ParameterError> {
+        self.config.validate()?;
+        let mut history = Vec::with_capacity(self.config.generations);
+        let base = self.template.plants.first().map(|p| p.genotype).unwrap_or_default();
+        let mut population: Vec<Genotype> = (0..self.config.population_size)
+            .map(|i| if i == 0 { base } else { self.mutate(&base, 1.0) })
+            .collect();
+        let mut best: Option<Individual> = None;
+
+        for generation in 0..self.config.generations {
+            let mut scored = self.evaluate(&population);
+            scored.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+            history.push(Self::stats(generation, &scored));
+            if best.as_ref().is_none_or(|b| scored[0].fitness > b.fitness) {
+                best = Some(scored[0].clone());
+            }
+            on_generation(&history[generation]);
+
+            population = self.next_generation(&scored);
+        }
+
+        let best = best.unwrap_or_else(|| self.evaluate(&[base]).remove(0));
+        Ok((history, best))
+    }
+
+    /// Scores each genotype on its own copy of the template, in parallel
+    pub fn evaluate(&self, population: &[Genotype]) -> Vec<Individual> {
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk = population.len().div_ceil(workers).max(1);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = population
+                .chunks(chunk)
+                .map(|genomes| scope.spawn(move || genomes.iter().map(|g| self.score(g)).collect::<Vec<_>>()))
+                .collect();
+            handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+        })
+    }
+
+    fn score(&self, genotype: &Genotype) -> Individual {
+        let mut env = self.template.clone();
+        env.reseed(self.config.seed);
+        for plant in &mut env.plants {
+            plant.genotype = *genotype;
+        }
+        let mut engine = PlantEngine::new(env);
+        let metrics = run_headless(&mut engine, self.config.steps, self.config.dt);
+        Individual {
+            genotype: *genotype,
+            fitness: self.config.weights.score(&metrics),
+            metrics,
+        }
+    }
+
+    fn next_generation(&mut self, scored: &[Individual]) -> Vec<Genotype> {
+        let mut next: Vec<Genotype> = scored
+            .iter()
+            .take(self.config.elitism)
+            .map(|i| i.genotype)
+            .collect();
+        while next.len() < self.config.population_size {
+            let a = self.tournament(scored);
+            let b = self.tournament(scored);
+            let child = if self.rng.gen::<f32>() < self.config.crossover_rate {
+                self.crossover(&a, &b)
+            } else {
+                a
+            };
+            let child = self.mutate(&child, self.config.mutation_rate);
+            next.push(child);
+        }
+        next
+    }
+
+    fn tournament(&mut self, scored: &[Individual]) -> Genotype {
+        let mut winner = &scored[self.rng.gen_range(0..scored.len())];
+        for _ in 1..self.config.tournament_size {
+            let challenger = &scored[self.rng.gen_range(0..scored.len())];
+            if challenger.fitness > winner.fitness {
+                winner = challenger;
+            }
+        }
+        winner.genotype
+    }
+
+    /// Blend crossover: each evolved gene is a random mix of the parents' values
+    fn crossover(&mut self, a: &Genotype, b: &Genotype) -> Genotype {
+        let mut child = *a;
+        for gene in &self.config.genes {
+            if let (Some(x), Some(y)) = (a.get(gene), b.get(gene)) {
+                let t: f32 = self.rng.gen();
+                child.set(gene, x + t * (y - x));
+            }
+        }
+        child
+    }
+
+    fn mutate(&mut self, genotype: &Genotype, rate: f32) -> Genotype {
+        let mut mutant = *genotype;
+        let scale = self.config.mutation_scale;
+        for gene in &self.config.genes {
+            if self.rng.gen::<f32>() >= rate {
+                continue;
+            }
+            if let Some(value) = genotype.get(gene) {
+                let factor = 1.0 + self.rng.gen_range(-scale..scale);
+                mutant.set(gene, (value * factor).max(0.0));
+            }
+        }
+        mutant
+    }
+
+    fn stats(generation: usize, scored: &[Individual]) -> GenerationStats {
+        let n = scored.len() as f32;
+        let mean = scored.iter().map(|i| i.fitness).sum::<f32>() / n;
+        let variance = scored.iter().map(|i| (i.fitness - mean).powi(2)).sum::<f32>() / n;
+        GenerationStats {
+            generation,
+            best: scored[0].fitness,
+            mean,
+            worst: scored[scored.len() - 1].fitness,
+            std_dev: variance.sqrt(),
+            best_genotype: scored[0].genotype,
+        }
+    }
+}
+
+/// Writes the fitness history as CSV: one row per generation, with the best genotype's evolved genes
+pub fn write_history_csv(path: &Path, genes: &[String], history: &[GenerationStats]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(out, "generation,best,mean,worst,std_dev")?;
+    for gene in genes {
+        write!(out, ",{}", gene)?;
+    }
+    writeln!(out)?;
+    for stats in history {
+        write!(
+            out,
+            "{},{},{},{},{}",
+            stats.generation, stats.best, stats.mean, stats.worst, stats.std_dev
+        )?;
+        for gene in genes {
+            write!(out, ",{}", stats.best_genotype.get(gene).unwrap_or(f32::NAN))?;
+        }
+        writeln!(out)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evolution_never_loses_the_elite() {
+        let config = EvolutionConfig {
+            population_size: 6,
+            generations: 3,
+            steps: 20,
+            ..EvolutionConfig::default()
+        };
+        let mut evolution = Evolution::new(config, SimulationEnv::with_seed(3));
+        let (history, best) = evolution.run().unwrap();
+        assert_eq!(history.len(), 3);
+        assert!(history.windows(2).all(|w| w[1].best >= w[0].best));
+        assert_eq!(best.fitness, history[2].best);
+
+        // Bad configs fail before any generation runs
+        evolution.config.genes.push("phototropsim".to_string());
+        assert_eq!(evolution.run().err(), Some(ParameterError::Unknown("phototropsim".to_string())));
+        evolution.config.population_size = 0;
+        assert!(matches!(evolution.run(), Err(ParameterError::Invalid { .. })));
+    }
+}
@@ -0,0 +1,6 @@
+// src/analysis/mod.rs
+pub mod bifurcation;
+pub mod calibration;
+pub mod dynamics;
+pub mod evolution;
+pub mod sensitivity;
@@ -0,0 +1,91 @@
+// src/bin/batch.rs
+use cs_simulator::simulation::batch::{
+    run_sweep, summarize, write_results_csv, write_summary_csv, SweepAxis, SweepMode, SweepSpec,
+};
+use cs_simulator::simulation::parameters::parameter_names;
+use cs_simulator::simulation::simulation_env::SimulationEnv;
+use std::env;
+use std::path::PathBuf;
+use std::time::Instant;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut spec = SweepSpec::default();
+    let mut samples = None;
+    let mut out = PathBuf::from("sweep_results.csv");
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--param", Some(v)) => match SweepAxis::parse(v) {
+                Ok(axis) => spec.axes.push(axis),
+                Err(e) => fail(&e),
+            },
+            ("--random", Some(v)) => samples = Some(parse(v)),
+            ("--steps", Some(v)) => spec.steps = parse(v),
+            ("--dt", Some(v)) => spec.dt = parse(v),
+            ("--workers", Some(v)) => spec.workers = parse(v),
+            ("--seed", Some(v)) => spec.seed = parse(v),
+            ("--out", Some(v)) => out = PathBuf::from(v),
+            (flag, _) => fail(&format!("unknown or incomplete option '{}'. Use 'batch --help' for usage.", flag)),
+        }
+        i += 2;
+    }
+    if spec.axes.is_empty() {
+        fail("no parameters to sweep; add at least one --param");
+    }
+    if let Some(samples) = samples {
+        spec.mode = SweepMode::Random { samples };
+    }
+
+    let configs = spec.configurations().len();
+    println!("Running {} configurations for {} steps on {} workers...", configs, spec.steps, spec.workers);
+    let started = Instant::now();
+    let rows = match run_sweep(&spec, &SimulationEnv::with_seed(spec.seed)) {
+        Ok(rows) => rows,
+        Err(e) => fail(&e.to_string()),
+    };
+    println!("Finished in {:.1}s\n", started.elapsed().as_secs_f32());
+
+    let summary = summarize(&rows);
+    for s in &summary {
+        println!("{}", s);
+    }
+
+    let summary_path = out.with_file_name(format!(
+        "{}_summary.csv",
+        out.file_stem().and_then(|s| s.to_str()).unwrap_or("sweep")
+    ));
+    match write_results_csv(&out, &spec, &rows).and_then(|_| write_summary_csv(&summary_path, &summary)) {
+        Ok(()) => println!("\nResults written to {} and {}", out.display(), summary_path.display()),
+        Err(e) => fail(&format!("failed to write results: {}", e)),
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value '{}'", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Batch Command Help:");
+    println!("Usage: batch --param <name=min:max[:points]> [--param ...] [options]");
+    println!("\nOptions:");
+    println!("  --param <spec>   Parameter to sweep; points defaults to 5 (repeatable)");
+    println!("  --random <n>     Draw n random configurations instead of a full grid");
+    println!("  --steps <n>      Steps per configuration (default 500)");
+    println!("  --dt <t>         Time step (default 0.1)");
+    println!("  --workers <n>    Worker threads (default: available cores)");
+    println!("  --seed <n>       Random seed (default 42)");
+    println!("  --out <file>     Results CSV (default sweep_results.csv); summary goes to <file>_summary.csv");
+    println!("\nParameters: {}", parameter_names().join(", "));
+}
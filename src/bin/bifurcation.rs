@@ -0,0 +1,102 @@
+// src/bin/bifurcation.rs
+use cs_simulator::analysis::bifurcation::{
+    bifurcation, write_regimes_csv, write_samples_csv, BifurcationConfig, BifurcationVariable,
+};
+use cs_simulator::simulation::batch::SweepAxis;
+use cs_simulator::simulation::simulation_env::SimulationEnv;
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut config = BifurcationConfig::new(SweepAxis::parse("d_critical=1:20:40").unwrap());
+    let mut out = PathBuf::from("bifurcation.csv");
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--continuation", _) => {
+                config.continuation = true;
+                i += 1;
+                continue;
+            }
+            ("--param", Some(v)) => match SweepAxis::parse(v) {
+                Ok(axis) => config.parameter = axis,
+                Err(e) => fail(&e),
+            },
+            ("--variable", Some(v)) => match BifurcationVariable::parse(v) {
+                Some(variable) => config.variable = variable,
+                None => fail(&format!("unknown variable '{}'", v)),
+            },
+            ("--transient", Some(v)) => config.transient_steps = parse(v),
+            ("--samples", Some(v)) => config.sample_steps = parse(v),
+            ("--dt", Some(v)) => config.dt = parse(v),
+            ("--seed", Some(v)) => config.seed = parse(v),
+            ("--out", Some(v)) => out = PathBuf::from(v),
+            (flag, _) => fail(&format!("unknown or incomplete option '{}'. Use 'bifurcation --help' for usage.", flag)),
+        }
+        i += 2;
+    }
+
+    let template = SimulationEnv::with_seed(config.seed);
+    println!(
+        "Scanning {} over [{}, {}] at {} values ({} + {} steps each)...",
+        config.parameter.name,
+        config.parameter.min,
+        config.parameter.max,
+        config.parameter.points,
+        config.transient_steps,
+        config.sample_steps
+    );
+    let points = bifurcation(&config, &template).unwrap_or_else(|e| fail(&e.to_string()));
+
+    let variable = config.variable;
+    println!("{:>12} {:<12} {:>12} {:>12}", config.parameter.name, "regime", "min", "max");
+    for point in &points {
+        let values = point.samples.iter().map(|s| s.get(variable));
+        let min = values.clone().fold(f32::NAN, f32::min);
+        let max = values.fold(f32::NAN, f32::max);
+        println!("{:>12.4} {:<12} {:>12.4} {:>12.4}", point.value, point.regime, min, max);
+    }
+
+    let regimes_path = out.with_file_name(format!(
+        "{}_regimes.csv",
+        out.file_stem().and_then(|s| s.to_str()).unwrap_or("bifurcation")
+    ));
+    if let Err(e) = write_samples_csv(&out, &config, &points) {
+        fail(&format!("failed to write {}: {}", out.display(), e));
+    }
+    if let Err(e) = write_regimes_csv(&regimes_path, &config, &points) {
+        fail(&format!("failed to write {}: {}", regimes_path.display(), e));
+    }
+    println!("\nSamples written to {}, regimes to {}", out.display(), regimes_path.display());
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value '{}'", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Bifurcation Command Help:");
+    println!("Usage: bifurcation [--param <name=min:max[:points]>] [options]");
+    println!("\nOptions:");
+    println!("  --param <spec>      Parameter to step, e.g. d_critical, alpha or beta (default d_critical=1:20:40)");
+    println!("  --variable <name>   Plotted and classified variable (default distortion)");
+    println!("  --transient <n>     Steps discarded at each value (default 500)");
+    println!("  --samples <n>       Attractor samples recorded at each value (default 200)");
+    println!("  --continuation      Start each value from the previous value's final H, D, T");
+    println!("  --dt <t>            Time step (default 0.1)");
+    println!("  --seed <n>          Random seed (default 42)");
+    println!("  --out <file>        Samples CSV (default bifurcation.csv; regimes go to <stem>_regimes.csv)");
+    println!("\nVariables: {}", BifurcationVariable::NAMES.join(", "));
+}
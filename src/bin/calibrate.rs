@@ -0,0 +1,118 @@
+// src/bin/calibrate.rs
+use cs_simulator::analysis::calibration::{
+    calibrate, load_trajectories, place_plants, write_parameters_csv, write_residuals_csv, CalibrationConfig,
+};
+use cs_simulator::simulation::batch::SweepAxis;
+use cs_simulator::simulation::parameters::parameter_names;
+use cs_simulator::simulation::scenario::Scenario;
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut config = CalibrationConfig::default();
+    let mut observed_path = None;
+    let mut fixed: Vec<(String, f32)> = Vec::new();
+    let mut scenario: Option<Scenario> = None;
+    let mut place = false;
+    let mut out = PathBuf::from("calibration.csv");
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--observed", Some(v)) => observed_path = Some(PathBuf::from(v)),
+            ("--param", Some(v)) => match SweepAxis::parse(v) {
+                Ok(axis) => config.parameters.push(axis),
+                Err(e) => fail(&e),
+            },
+            ("--set", Some(v)) => match v.split_once('=') {
+                Some((name, value)) => fixed.push((name.trim().to_string(), parse(value.trim()))),
+                None => fail(&format!("expected name=value, got '{}'", v)),
+            },
+            ("--scenario", Some(v)) => match Scenario::open(v) {
+                Ok(loaded) => scenario = Some(loaded),
+                Err(e) => fail(&format!("{}: {}", v, e)),
+            },
+            ("--place", _) => {
+                place = true;
+                i += 1;
+                continue;
+            }
+            ("--dt", Some(v)) => config.dt = parse(v),
+            ("--seed", Some(v)) => config.seed = parse(v),
+            ("--max-evals", Some(v)) => config.max_evaluations = parse(v),
+            ("--tolerance", Some(v)) => config.tolerance = parse(v),
+            ("--out", Some(v)) => out = PathBuf::from(v),
+            (flag, _) => fail(&format!("unknown or incomplete option '{}'. Use 'calibrate --help' for usage.", flag)),
+        }
+        i += 2;
+    }
+    let Some(observed_path) = observed_path else { fail("no observations; pass --observed <file.csv>") };
+    if config.parameters.is_empty() {
+        fail("no parameters; add at least one --param");
+    }
+
+    let observed = load_trajectories(&observed_path)
+        .unwrap_or_else(|e| fail(&format!("failed to read {}: {}", observed_path.display(), e)));
+    let count: usize = observed.iter().map(|t| t.samples.len()).sum();
+
+    // Experimental conditions that are known rather than fitted
+    let scenario = scenario.unwrap_or(Scenario { seed: config.seed, ..Scenario::default() });
+    let mut template = scenario.build().unwrap_or_else(|e| fail(&e.to_string()));
+    for (name, value) in &fixed {
+        template.set_parameter(name, *value).unwrap_or_else(|e| fail(&e.to_string()));
+    }
+    if place {
+        place_plants(&mut template, &observed).unwrap_or_else(|e| fail(&e.to_string()));
+    }
+
+    println!("Calibrating {} parameter(s) against {} observations...", config.parameters.len(), count);
+    let result = calibrate(&config, &template, &observed).unwrap_or_else(|e| fail(&e.to_string()));
+    for (name, value) in &result.parameters {
+        println!("  {:<22} {:>10.5}", name, value);
+    }
+    println!("SSE={:.5} RMSE={:.5} ({} evaluations)", result.sse, result.rmse, result.evaluations);
+
+    let residuals_path = out.with_file_name(format!(
+        "{}_residuals.csv",
+        out.file_stem().and_then(|s| s.to_str()).unwrap_or("calibration")
+    ));
+    if let Err(e) = write_parameters_csv(&out, &result) {
+        fail(&format!("failed to write {}: {}", out.display(), e));
+    }
+    if let Err(e) = write_residuals_csv(&residuals_path, &result.residuals) {
+        fail(&format!("failed to write {}: {}", residuals_path.display(), e));
+    }
+    println!("\nParameters written to {}, residuals to {}", out.display(), residuals_path.display());
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value '{}'", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Calibrate Command Help:");
+    println!("Usage: calibrate --observed <file.csv> --param <name=min:max> [--param ...] [options]");
+    println!("\nOptions:");
+    println!("  --observed <file>  Observed stem-tip CSV with columns time,x,y[,z][,plant_id]");
+    println!("  --param <spec>     Parameter to fit within [min, max] (repeatable)");
+    println!("  --scenario <name>  Model to fit: a bundled scenario or a scenario file (default: the default scene)");
+    println!("  --place            Start each observed plant with its stem tip at its first observation");
+    println!("  --set <name=v>     Fix a known condition, e.g. light_intensity=1.2 (repeatable)");
+    println!("  --dt <t>           Time step (default 0.1)");
+    println!("  --seed <n>         Random seed for the model noise (default 42)");
+    println!("  --max-evals <n>    Maximum model evaluations (default 400)");
+    println!("  --tolerance <t>    Stop when the simplex spread falls below this (default 1e-6)");
+    println!("  --out <file>       Fitted parameters CSV (default calibration.csv; residuals go to <stem>_residuals.csv)");
+    println!("\nParameters: {}", parameter_names().join(", "));
+}
@@ -0,0 +1,82 @@
+// src/bin/dynamics.rs
+use cs_simulator::analysis::dynamics::DynamicsAnalysis;
+use cs_simulator::simulation::simulation_env::SimulationEnv;
+use std::env;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut seed = 42;
+    let mut dt = 0.1;
+    let mut steps = 5000;
+    let mut transient = 100;
+    let mut h_range = 5.0;
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--seed", Some(v)) => seed = parse(v),
+            ("--dt", Some(v)) => dt = parse(v),
+            ("--steps", Some(v)) => steps = parse(v),
+            ("--transient", Some(v)) => transient = parse(v),
+            ("--h-range", Some(v)) => h_range = parse(v),
+            (flag, _) => fail(&format!("unknown or incomplete option '{}'. Use 'dynamics --help' for usage.", flag)),
+        }
+        i += 2;
+    }
+
+    let env = SimulationEnv::with_seed(seed);
+    let Some(plant) = env.plants.first() else { fail("the default scene has no plants") };
+    let analysis = DynamicsAnalysis::for_plant(plant, &env.environment, dt);
+    let p = &analysis.params;
+    println!(
+        "H–D–T dynamics for plant {} ({}): α={:.3} β={:.3} E={:.3} ∇S={:.3} dt={}",
+        plant.id, plant.species, p.alpha, p.beta, p.energy, p.grad_s, dt
+    );
+
+    println!("\nFixed points (samples of the balance curve D = -∇S·H):");
+    let points = analysis.fixed_points(&analysis.default_guesses(h_range, 9));
+    if points.is_empty() {
+        println!("  none found with D in [0, {}]", analysis.d_critical);
+    }
+    for point in &points {
+        let moduli: Vec<String> = point.eigenvalues.iter().map(|l| format!("{:.5}", l.re.hypot(l.im))).collect();
+        println!(
+            "  H={:>9.4} D={:>9.4} T={:>9.4}  {:<8} |λ|=[{}]",
+            point.state.x, point.state.y, point.state.z, point.stability, moduli.join(", ")
+        );
+    }
+
+    let estimate = analysis.lyapunov_exponent(DynamicsAnalysis::state_of(plant), steps, transient);
+    println!(
+        "\nLargest Lyapunov exponent from the current state: {:.6} per unit time over {} steps{}",
+        estimate.exponent,
+        estimate.steps,
+        if estimate.collapsed { " (collapsed past d_critical)" } else { "" }
+    );
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value '{}'", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Dynamics Command Help:");
+    println!("Usage: dynamics [options]");
+    println!("\nAnalyses the H–D–T equations for the first plant of the default scene.");
+    println!("\nOptions:");
+    println!("  --seed <n>       Scene seed (default 42)");
+    println!("  --dt <t>         Time step of the map (default 0.1)");
+    println!("  --steps <n>      Steps averaged for the Lyapunov exponent (default 5000)");
+    println!("  --transient <n>  Steps discarded first (default 100)");
+    println!("  --h-range <h>    Search fixed points over H in [-h, h] (default 5)");
+}
@@ -0,0 +1,79 @@
+// src/bin/evolve.rs
+use cs_simulator::analysis::evolution::{write_history_csv, Evolution, EvolutionConfig};
+use cs_simulator::simulation::simulation_env::SimulationEnv;
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut config = EvolutionConfig::default();
+    let mut out = PathBuf::from("fitness_history.csv");
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--generations", Some(v)) => config.generations = parse(v),
+            ("--population", Some(v)) => config.population_size = parse(v),
+            ("--steps", Some(v)) => config.steps = parse(v),
+            ("--dt", Some(v)) => config.dt = parse(v),
+            ("--seed", Some(v)) => config.seed = parse(v),
+            ("--genes", Some(v)) => config.genes = v.split(',').map(|g| g.trim().to_string()).collect(),
+            ("--out", Some(v)) => out = PathBuf::from(v),
+            (flag, _) => {
+                eprintln!("Unknown or incomplete option: '{}'. Use 'evolve --help' for usage.", flag);
+                return;
+            }
+        }
+        i += 2;
+    }
+
+    println!(
+        "Evolving {} genes over {} generations (population {}, {} steps each)...",
+        config.genes.len(), config.generations, config.population_size, config.steps
+    );
+    let genes = config.genes.clone();
+    let mut evolution = Evolution::new(config.clone(), SimulationEnv::with_seed(config.seed));
+    let result = evolution.run_with(|stats| {
+        println!("Generation {}: best={:.3} mean={:.3}", stats.generation, stats.best, stats.mean);
+    });
+    let (history, best) = result.unwrap_or_else(|e| fail(&e.to_string()));
+
+    println!("\nBest fitness: {:.3}", best.fitness);
+    for gene in &genes {
+        println!("  {:<20} {:.4}", gene, best.genotype.get(gene).expect("genes are validated"));
+    }
+    match write_history_csv(&out, &genes, &history) {
+        Ok(()) => println!("Fitness history written to {}", out.display()),
+        Err(e) => eprintln!("Failed to write {}: {}", out.display(), e),
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| {
+        eprintln!("Invalid value: '{}'", value);
+        std::process::exit(1);
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Evolve Command Help:");
+    println!("Usage: evolve [options]");
+    println!("\nOptions:");
+    println!("  --generations <n>  Number of generations (default 20)");
+    println!("  --population <n>   Individuals per generation (default 24)");
+    println!("  --steps <n>        Simulation steps per evaluation (default 300)");
+    println!("  --dt <t>           Time step (default 0.1)");
+    println!("  --seed <n>         Random seed (default 42)");
+    println!("  --genes <a,b,..>   Genotype parameters to evolve (default: tropism sensitivities)");
+    println!("  --out <file>       Fitness history CSV (default fitness_history.csv)");
+}
@@ -2,7 +2,13 @@
 fn main() {
     println!("Available commands:");
     println!("  help      - Display this help message");
-    println!("  simulator - Interact with the simulation (use 'simulator --help' for subcommands)");
+    println!("  simulator - Run the simulation headlessly and write per-step CSV (use 'simulator --help' for options)");
+    println!("  batch     - Run a headless parameter sweep (cargo run --bin batch -- --help)");
+    println!("  sensitivity - Morris / Sobol sensitivity analysis (cargo run --bin sensitivity -- --help)");
+    println!("  calibrate - Fit parameters to observed growth trajectories (cargo run --bin calibrate -- --help)");
+    println!("  dynamics  - Fixed points, stability and Lyapunov exponent of H–D–T (cargo run --bin dynamics -- --help)");
+    println!("  bifurcation - Bifurcation diagram over d_critical, alpha or beta (cargo run --bin bifurcation -- --help)");
+    println!("  evolve    - Evolve plant genotypes with a genetic algorithm (cargo run --bin evolve -- --help)");
     println!("  exit      - Exit the CLI");
     println!("\nFor more details on a command, use the command with '--help'.");
 }
\ No newline at end of file
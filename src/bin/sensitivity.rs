@@ -0,0 +1,105 @@
+// src/bin/sensitivity.rs
+use cs_simulator::analysis::sensitivity::{morris, sobol, write_morris_csv, write_sobol_csv, SensitivityConfig};
+use cs_simulator::simulation::batch::SweepAxis;
+use cs_simulator::simulation::headless::RunMetrics;
+use cs_simulator::simulation::parameters::parameter_names;
+use cs_simulator::simulation::simulation_env::SimulationEnv;
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut config = SensitivityConfig::default();
+    let mut metrics = Vec::new();
+    let mut method = "morris".to_string();
+    let mut samples = 10;
+    let mut out = PathBuf::from("sensitivity.csv");
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--method", Some(v)) => method = v.clone(),
+            ("--param", Some(v)) => match SweepAxis::parse(v) {
+                Ok(axis) => config.parameters.push(axis),
+                Err(e) => fail(&e),
+            },
+            ("--metric", Some(v)) => metrics.push(v.clone()),
+            ("--samples", Some(v)) => samples = parse(v),
+            ("--steps", Some(v)) => config.steps = parse(v),
+            ("--dt", Some(v)) => config.dt = parse(v),
+            ("--workers", Some(v)) => config.workers = parse(v),
+            ("--seed", Some(v)) => config.seed = parse(v),
+            ("--out", Some(v)) => out = PathBuf::from(v),
+            (flag, _) => fail(&format!("unknown or incomplete option '{}'. Use 'sensitivity --help' for usage.", flag)),
+        }
+        i += 2;
+    }
+    if config.parameters.is_empty() {
+        fail("no parameters; add at least one --param");
+    }
+    if !metrics.is_empty() {
+        config.metrics = metrics;
+    }
+    let template = SimulationEnv::with_seed(config.seed);
+    let k = config.parameters.len();
+
+    match method.as_str() {
+        "morris" => {
+            println!("Morris screening: {} trajectories, {} simulations...", samples, samples * (k + 1));
+            let results = morris(&config, &template, samples).unwrap_or_else(|e| fail(&e.to_string()));
+            println!("{:<18} {:<22} {:>10} {:>10} {:>10}", "metric", "parameter", "mu", "mu*", "sigma");
+            for r in &results {
+                println!("{:<18} {:<22} {:>10.4} {:>10.4} {:>10.4}", r.metric, r.parameter, r.mu, r.mu_star, r.sigma);
+            }
+            report(write_morris_csv(&out, &results), &out);
+        }
+        "sobol" => {
+            println!("Sobol indices: {} base samples, {} simulations...", samples, samples * (k + 2));
+            let results = sobol(&config, &template, samples).unwrap_or_else(|e| fail(&e.to_string()));
+            println!("{:<18} {:<22} {:>10} {:>10}", "metric", "parameter", "S1", "ST");
+            for r in &results {
+                println!("{:<18} {:<22} {:>10.4} {:>10.4}", r.metric, r.parameter, r.first_order, r.total_order);
+            }
+            report(write_sobol_csv(&out, &results), &out);
+        }
+        other => fail(&format!("unknown method '{}'; use morris or sobol", other)),
+    }
+}
+
+fn report(result: std::io::Result<()>, out: &std::path::Path) {
+    match result {
+        Ok(()) => println!("\nIndices written to {}", out.display()),
+        Err(e) => fail(&format!("failed to write {}: {}", out.display(), e)),
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value '{}'", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Sensitivity Command Help:");
+    println!("Usage: sensitivity --method <morris|sobol> --param <name=min:max[:levels]> [--param ...] [options]");
+    println!("\nOptions:");
+    println!("  --method <m>     morris (elementary effects) or sobol (Saltelli sampling), default morris");
+    println!("  --param <spec>   Parameter range; levels is used by Morris (default 5) (repeatable)");
+    println!("  --metric <name>  Output metric to analyse (repeatable; default final_rho_c, final_coherence)");
+    println!("  --samples <n>    Morris trajectories or Sobol base samples (default 10)");
+    println!("  --steps <n>      Steps per simulation (default 300)");
+    println!("  --dt <t>         Time step (default 0.1)");
+    println!("  --workers <n>    Worker threads (default: available cores)");
+    println!("  --seed <n>       Random seed (default 42)");
+    println!("  --out <file>     Indices CSV (default sensitivity.csv)");
+    println!("\nParameters: {}", parameter_names().join(", "));
+    println!("Metrics: {}", RunMetrics::METRICS.join(", "));
+}
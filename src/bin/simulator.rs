@@ -1,30 +1,322 @@
 // src/bin/simulator.rs
+use cs_simulator::plants::metric::MetricFormulation;
+#[cfg(feature = "render")]
+use cs_simulator::render::{Scene, TimeLapse, TimeLapseConfig};
+use cs_simulator::simulation::checkpoint::Checkpoint;
+use cs_simulator::simulation::export::{ExportFormat, Exporter, PlantRow};
+use cs_simulator::simulation::geometry::{export_geometry, GeometryFormat, GeometryOptions};
+use cs_simulator::simulation::headless::{write_steps_csv, StepRecord};
+use cs_simulator::simulation::parameters::parameter_names;
+use cs_simulator::simulation::recording::Recorder;
+use cs_simulator::simulation::scenario::{Scenario, EXAMPLES};
+use cs_simulator::simulation::simulation_runner::{SimulationEvent, SimulationRunner};
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Instant;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 {
-        println!("Usage: simulator <command>");
-        println!("Commands: start, stop, status, reset");
-        println!("Run 'simulator --help' for more information.");
-        return;
-    }
-
-    match args[1].as_str() {
-        "--help" | "-h" => {
-            println!("Simulator Command Help:");
-            println!("Usage: simulator <command>");
-            println!("\nCommands:");
-            println!("  start  - Start the simulation");
-            println!("  stop   - Stop the simulation");
-            println!("  status - Check simulation status");
-            println!("  reset  - Reset the simulation to initial state");
-        }
-        "start" => println!("Starting simulation... (placeholder)"),
-        "stop" => println!("Stopping simulation... (placeholder)"),
-        "status" => println!("Simulation status: Running (placeholder)"),
-        "reset" => println!("Resetting simulation... (placeholder)"),
-        cmd => println!("Unknown simulator command: '{}'. Use 'simulator --help' for usage.", cmd),
-    }
-}
\ No newline at end of file
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut scenario = Scenario::default();
+    let mut steps: usize = 100;
+    let mut dt: Option<f32> = None;
+    let mut every: usize = 10;
+    let mut out = PathBuf::from("simulation.csv");
+    let mut resume: Option<PathBuf> = None;
+    let mut checkpoint: Option<PathBuf> = None;
+    let mut recording: Option<PathBuf> = None;
+    let mut export: Option<PathBuf> = None;
+    let mut geometry: Option<PathBuf> = None;
+    let mut geometry_options = GeometryOptions::default();
+    let mut frames: Option<PathBuf> = None;
+    let mut timelapse: Option<PathBuf> = None;
+    let mut frame_every: u64 = 10;
+    let mut frame_size: (i32, i32) = (400, 400);
+    let mut fps: u16 = 10;
+    let mut scene_option: Option<&str> = None; // The first option that only shapes a new scene
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--help" | "-h", _) => {
+                print_help();
+                return;
+            }
+            ("--list-scenarios", _) => {
+                for (name, _) in EXAMPLES {
+                    if let Some(example) = Scenario::example(name) {
+                        println!("{:<12} {}", name, example.description);
+                    }
+                }
+                return;
+            }
+            ("--scenario", Some(v)) => {
+                match Scenario::open(v) {
+                    Ok(loaded) => scenario = loaded,
+                    Err(e) => fail(&format!("{}: {}", v, e)),
+                }
+                scene_option.get_or_insert("--scenario");
+            }
+            ("--steps", Some(v)) => steps = parse(v),
+            ("--dt", Some(v)) => dt = Some(parse(v)),
+            ("--seed", Some(v)) => {
+                scenario.seed = parse(v);
+                scene_option.get_or_insert("--seed");
+            }
+            ("--plant", Some(v)) => {
+                match Scenario::parse_plant(v) {
+                    Ok(plant) => scenario.plants.push(plant),
+                    Err(e) => fail(&e),
+                }
+                scene_option.get_or_insert("--plant");
+            }
+            ("--set", Some(v)) => match v.split_once('=') {
+                Some((name, value)) => scenario.parameters.push((name.trim().to_string(), parse(value.trim()))),
+                None => fail(&format!("expected name=value, got '{}'", v)),
+            },
+            ("--metric", Some(v)) => match MetricFormulation::parse(v) {
+                Some(formulation) => scenario.metric = Some(formulation),
+                None => fail(&format!("unknown metric formulation '{}'", v)),
+            },
+            ("--every", Some(v)) => every = parse(v),
+            ("--out", Some(v)) => out = PathBuf::from(v),
+            ("--resume", Some(v)) => resume = Some(PathBuf::from(v)),
+            ("--checkpoint", Some(v)) => checkpoint = Some(PathBuf::from(v)),
+            ("--record", Some(v)) => recording = Some(PathBuf::from(v)),
+            ("--geometry", Some(v)) => match GeometryFormat::from_path(Path::new(v)) {
+                Some(_) => geometry = Some(PathBuf::from(v)),
+                None => fail(&format!("cannot export geometry to '{}': use a .obj, .ply or .vtk file", v)),
+            },
+            ("--geometry-attributes", _) => {
+                geometry_options.attributes = true;
+                i += 1;
+                continue;
+            }
+            ("--frames", Some(v)) => frames = Some(PathBuf::from(v)),
+            ("--timelapse", Some(v)) => timelapse = Some(PathBuf::from(v)),
+            ("--frame-every", Some(v)) => frame_every = parse(v),
+            ("--frame-size", Some(v)) => match v.split_once('x') {
+                Some((w, h)) => frame_size = (parse(w), parse(h)),
+                None => fail(&format!("expected WIDTHxHEIGHT, got '{}'", v)),
+            },
+            ("--fps", Some(v)) => fps = parse(v),
+            ("--export", Some(v)) => match ExportFormat::from_path(Path::new(v)) {
+                Some(_) => export = Some(PathBuf::from(v)),
+                None => fail(&format!("cannot export to '{}': use a .csv or .npy file", v)),
+            },
+            (flag, _) => fail(&format!("unknown or incomplete option '{}'. Use 'simulator --help' for usage.", flag)),
+        }
+        i += 2;
+    }
+
+    // A checkpoint replaces the scenario and supplies the timestep unless --dt is given;
+    // --set and --metric still apply on top of it
+    if let (Some(_), Some(option)) = (&resume, scene_option) {
+        fail(&format!("{} cannot be combined with --resume: the checkpoint already holds the scene", option));
+    }
+    let (env, dt) = match &resume {
+        Some(path) => match Checkpoint::load(path) {
+            Ok(checkpoint) => {
+                let mut env = checkpoint.env;
+                if let Some(formulation) = scenario.metric {
+                    env.environment.metric.formulation = formulation;
+                }
+                for (name, value) in &scenario.parameters {
+                    if let Err(e) = env.set_parameter(name, *value) {
+                        fail(&e.to_string());
+                    }
+                }
+                (env, dt.unwrap_or(checkpoint.dt))
+            }
+            Err(e) => fail(&format!("failed to load {}: {}", path.display(), e)),
+        },
+        None => match scenario.build() {
+            Ok(env) => (env, dt.unwrap_or(SimulationRunner::DT)),
+            Err(e) => fail(&e.to_string()),
+        },
+    };
+    if dt <= 0.0 || !dt.is_finite() {
+        fail("--dt must be positive");
+    }
+    let seed = env.seed;
+    let (mut runner, _commands) = SimulationRunner::new(env);
+    if let Some(path) = &recording {
+        match Recorder::create(path) {
+            Ok(recorder) => runner.subscribe(Box::new(recorder)),
+            Err(e) => fail(&format!("failed to create {}: {}", path.display(), e)),
+        }
+    }
+    // The exporter stays here so it can be finished and its errors reported; step results reach it over a channel
+    let mut exporter = export.as_ref().map(|path| {
+        let format = ExportFormat::from_path(path).expect("checked while parsing options");
+        let exporter = Exporter::create(path, format)
+            .unwrap_or_else(|e| fail(&format!("failed to create {}: {}", path.display(), e)));
+        let (events, received) = mpsc::channel();
+        runner.subscribe(Box::new(events));
+        (exporter, received)
+    });
+
+    // Like the exporter, the time-lapse is driven from the step loop so its errors end the run
+    #[cfg(feature = "render")]
+    let mut time_lapse: Option<(TimeLapse, usize)> = None; // With the number of frames rendered
+    if frames.is_some() || timelapse.is_some() {
+        #[cfg(feature = "render")]
+        {
+            if frame_every == 0 {
+                fail("--frame-every must be at least 1");
+            }
+            if frame_size.0 <= 0 || frame_size.1 <= 0 {
+                fail(&format!("--frame-size must be at least 1x1, got {}x{}", frame_size.0, frame_size.1));
+            }
+            let config = TimeLapseConfig {
+                every: frame_every,
+                width: frame_size.0,
+                height: frame_size.1,
+                frames_dir: frames.clone(),
+                animation: timelapse.clone(),
+                fps,
+            };
+            match TimeLapse::new(config) {
+                Ok(created) => time_lapse = Some((created, 0)),
+                Err(e) => fail(&format!("failed to set up frames: {}", e)),
+            }
+        }
+        #[cfg(not(feature = "render"))]
+        {
+            let _ = (frame_every, frame_size, fps);
+            fail("--frames and --timelapse need the 'render' feature (cargo run --no-default-features --features render --bin simulator)");
+        }
+    }
+
+    match &resume {
+        Some(path) => println!("Resuming {} at step {}: {} more steps at dt={}...", path.display(), runner.env().step, steps, dt),
+        None => println!("Running {} steps at dt={} (seed {})...", steps, dt, seed),
+    }
+    let started = Instant::now();
+    let mut records = Vec::with_capacity(steps);
+    for _ in 0..steps {
+        let snapshot = runner.step(dt);
+        if let (Some(path), Some((exporter, received))) = (&export, &mut exporter) {
+            for event in received.try_iter() {
+                if let SimulationEvent::Step(results) = event {
+                    if let Err(e) = exporter.write(&PlantRow::rows(runner.env(), &results)) {
+                        fail(&format!("failed to write {}: {}", path.display(), e));
+                    }
+                }
+            }
+        }
+        #[cfg(feature = "render")]
+        if let Some((time_lapse, rendered)) = &mut time_lapse {
+            let env = runner.env();
+            if env.step.is_multiple_of(frame_every) {
+                if let Err(e) = time_lapse.capture(&Scene::of(env, Some(&snapshot)), env.step) {
+                    fail(&format!("failed to render step {}: {}", env.step, e));
+                }
+                *rendered += 1;
+            }
+        }
+        let record = StepRecord::of(runner.env(), &snapshot);
+        if every > 0 && record.step.is_multiple_of(every as u64) {
+            println!("{}", record);
+        }
+        records.push(record);
+    }
+    let elapsed = started.elapsed().as_secs_f32();
+    let env = runner.env().clone();
+    drop(runner); // Flushes the recording
+    if let (Some(path), Some((mut exporter, _))) = (&export, exporter) {
+        if let Err(e) = exporter.finish() {
+            fail(&format!("failed to write {}: {}", path.display(), e));
+        }
+    }
+    #[cfg(feature = "render")]
+    if let Some((mut time_lapse, rendered)) = time_lapse {
+        let finished = match rendered {
+            0 => Err(format!("no frames rendered: no step was a multiple of --frame-every {}", frame_every)),
+            _ => time_lapse.finish().map(|_| ()).map_err(|e| e.to_string()),
+        };
+        if let Err(e) = finished {
+            if let Some(path) = &timelapse {
+                let _ = std::fs::remove_file(path); // Incomplete
+            }
+            fail(&e);
+        }
+    }
+
+    println!("\nFinished in {:.2}s ({:.0} steps/s)", elapsed, steps as f32 / elapsed.max(1e-6));
+    if let Some(last) = records.last() {
+        println!("{}", last);
+        for (observable, value) in &last.observables {
+            println!("  {:<18} {:.4}", observable.name(), value);
+        }
+    }
+    match write_steps_csv(&out, &records) {
+        Ok(()) => println!("Per-step results written to {}", out.display()),
+        Err(e) => fail(&format!("failed to write {}: {}", out.display(), e)),
+    }
+    if let Some(path) = &recording {
+        println!("Recording written to {} (replay with: cs_simulator --replay {})", path.display(), path.display());
+    }
+    if let Some(path) = &export {
+        println!("Per-plant rows exported to {}", path.display());
+    }
+    if let Some(dir) = &frames {
+        println!("Frames written to {}", dir.display());
+    }
+    if let Some(path) = &timelapse {
+        println!("Time-lapse written to {}", path.display());
+    }
+    if let Some(path) = &geometry {
+        match export_geometry(path, &env.plants, &env.environment, &geometry_options) {
+            Ok(()) => println!("Plant geometry written to {}", path.display()),
+            Err(e) => fail(&format!("failed to write {}: {}", path.display(), e)),
+        }
+    }
+    if let Some(path) = checkpoint {
+        let checkpoint = Checkpoint { env, dt };
+        match checkpoint.save(&path) {
+            Ok(()) => println!("Checkpoint written to {} (continue with --resume)", path.display()),
+            Err(e) => fail(&format!("failed to write {}: {}", path.display(), e)),
+        }
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail(&format!("invalid value '{}'", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn print_help() {
+    println!("Simulator Command Help:");
+    println!("Usage: simulator [options]");
+    println!("Runs the simulation headlessly (no GTK needed: cargo run --no-default-features --bin simulator).");
+    println!("\nOptions:");
+    println!("  --scenario <name>     Start from a bundled scenario or a TOML scenario file; later options override it");
+    println!("  --list-scenarios      List the bundled scenarios");
+    println!("  --steps <n>           Steps to run (default 100)");
+    println!("  --dt <t>              Time step (default 0.1)");
+    println!("  --seed <n>            Random seed of the default scene (default 42)");
+    println!("  --plant <species@x,y> Plant a founder, e.g. 'Deep Root@-2,0' (repeatable; replaces the default plant)");
+    println!("  --set <name=v>        Set a parameter, e.g. alpha=0.1 (repeatable)");
+    println!("  --metric <name>       Metric formulation: diagonal, coherent or coupled");
+    println!("  --every <n>           Print a line every n steps; 0 prints only the summary (default 10)");
+    println!("  --out <file>          Per-step CSV (default simulation.csv)");
+    println!("  --resume <file>       Continue from a checkpoint instead of building a scenario");
+    println!("                        (--set and --metric apply on top; --scenario, --plant and --seed are rejected)");
+    println!("  --checkpoint <file>   Save a checkpoint after the last step");
+    println!("  --record <file>       Record every step for replay in the GUI");
+    println!("  --export <file>       Export one row per plant per step to a .csv or .npy (NumPy) file");
+    println!("  --geometry <file>     Write the final plants, obstacles and sources as a .obj, .ply or .vtk mesh");
+    println!("  --geometry-attributes Add per-vertex ρ_c, coherence and distortion to the mesh");
+    println!("  --frames <dir>        Render a PNG frame every --frame-every steps into <dir> (needs the 'render' feature)");
+    println!("  --timelapse <file>    Join the rendered frames into an animated PNG (.png or .apng)");
+    println!("  --frame-every <n>     Steps between rendered frames (default 10)");
+    println!("  --frame-size <WxH>    Frame size in pixels (default 400x400)");
+    println!("  --fps <n>             Time-lapse frame rate (default 10)");
+    println!("\nParameters: {}", parameter_names().join(", "));
+}
@@ -1,99 +1,194 @@
-use crate::engines::plant_engine::PlantEngine;
+use crate::engines::snapshot::CSpaceSnapshot;
+use crate::simulation::checkpoint::Checkpoint;
+use crate::simulation::mutation::{Mutation, MutationOutcome, MutationResult};
 use crate::simulation::simulation_env::SimulationEnv;
-use crate::plants::{Environment, Plant};
-use glib::source::{idle_add, SourceId};
+use crate::simulation::simulation_runner::{
+    ControlCommand, EventSource, ObserverId, RunnerStatus, SimulationEvent, SimulationObserver, Speed,
+};
+use crate::plants::{Environment, MetricModel, Plant, Species};
+use nalgebra::Vector3;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+/// How long a blocking request waits for the runner to answer
+const REPLY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Handle on a `SimulationRunner` for the GUI: sends it commands and mirrors its state
 #[derive(Clone)]
 pub struct SimulationControl {
-    engine: Arc<Mutex<PlantEngine>>,
+    commands: Sender<ControlCommand>,
+    env: Arc<Mutex<SimulationEnv>>,
     plants: Arc<Mutex<Vec<Plant>>>,
     environment: Arc<Mutex<Environment>>,
     logs: Arc<Mutex<Vec<String>>>,
-    running: Arc<Mutex<bool>>,
-    source_id: Arc<Mutex<Option<SourceId>>>,
+    snapshot: Arc<Mutex<Option<CSpaceSnapshot>>>,
+    status: Arc<Mutex<RunnerStatus>>,
 }
 
-impl SimulationControl {
-    pub fn new(env: SimulationEnv) -> Self {
-        let engine = Arc::new(Mutex::new(PlantEngine::new(env)));
-        let plants = Arc::new(Mutex::new(engine.lock().unwrap().env.plants.clone()));
-        let environment = Arc::new(Mutex::new(engine.lock().unwrap().env.environment.clone()));
-        let logs = Arc::new(Mutex::new(Vec::new()));
-        let running = Arc::new(Mutex::new(false));
-        let source_id = Arc::new(Mutex::new(None));
-
-        SimulationControl {
-            engine,
-            plants,
-            environment,
-            logs,
-            running,
-            source_id,
-        }
-    }
+/// Observer that copies runner state into the shared mirrors
+struct Mirror {
+    env: Arc<Mutex<SimulationEnv>>,
+    plants: Arc<Mutex<Vec<Plant>>>,
+    environment: Arc<Mutex<Environment>>,
+    logs: Arc<Mutex<Vec<String>>>,
+    snapshot: Arc<Mutex<Option<CSpaceSnapshot>>>,
+    status: Arc<Mutex<RunnerStatus>>,
+}
 
-    // Rest of the code (start, stop, getters, etc.) remains unchanged
-    pub fn start(&self) {
-        let mut running = self.running.lock().unwrap();
-        if *running {
-            return; // Already running
-        }
-        *running = true;
-
-        let engine = self.engine.clone();
-        let plants = self.plants.clone();
-        let environment = self.environment.clone();
-        let logs = self.logs.clone();
-        let running_clone = self.running.clone();
-        let source_id = self.source_id.clone();
-
-        let id = idle_add(move || {
-            let dt = 0.1;
-            let mut engine = engine.lock().unwrap();
-            let results = engine.update(dt);
-
-            {
-                let mut logs = logs.lock().unwrap();
-                for plant_results in results {
-                    for result in plant_results {
-                        logs.push(result.log);
-                        if logs.len() > 100 {
-                            logs.remove(0);
-                        }
-                    }
+impl SimulationObserver for Mirror {
+    fn notify(&mut self, env: &SimulationEnv, event: &SimulationEvent) -> bool {
+        match event {
+            SimulationEvent::Step(results) => {
+                let mut logs = self.logs.lock().unwrap();
+                for result in results.iter().flatten() {
+                    logs.push(result.to_string());
                 }
+                let excess = logs.len().saturating_sub(100);
+                logs.drain(..excess);
             }
-
-            {
-                let mut plants = plants.lock().unwrap();
-                *plants = engine.env.plants.clone();
+            SimulationEvent::Snapshot(snapshot) => {
+                // Sent after every step and reset, so this is where the world is copied
+                *self.snapshot.lock().unwrap() = Some(snapshot.clone());
+                *self.env.lock().unwrap() = env.clone();
+                *self.plants.lock().unwrap() = env.plants.clone();
+                *self.environment.lock().unwrap() = env.environment.clone();
             }
-            {
-                let mut env = environment.lock().unwrap();
-                *env = engine.env.environment.clone();
+            SimulationEvent::Status(status) => *self.status.lock().unwrap() = status.clone(),
+            SimulationEvent::Mutation(mutation, Err(e)) => {
+                self.logs.lock().unwrap().push(format!("Rejected {}: {}", mutation, e));
             }
+            SimulationEvent::Mutation(..) => {}
+        }
+        true
+    }
+}
 
-            if *running_clone.lock().unwrap() {
-                glib::ControlFlow::Continue
-            } else {
-                glib::ControlFlow::Break
-            }
-        });
+impl SimulationControl {
+    /// Subscribes a mirror to `source` (a runner or a replay); `commands` is the sender its `new` returned
+    pub fn attach(source: &mut impl EventSource, commands: Sender<ControlCommand>) -> Self {
+        let env = source.env();
+        let control = SimulationControl {
+            commands,
+            env: Arc::new(Mutex::new(env.clone())),
+            plants: Arc::new(Mutex::new(env.plants.clone())),
+            environment: Arc::new(Mutex::new(env.environment.clone())),
+            logs: Arc::new(Mutex::new(Vec::new())),
+            snapshot: Arc::new(Mutex::new(None)),
+            status: Arc::new(Mutex::new(source.status())),
+        };
+        source.subscribe(Box::new(Mirror {
+            env: control.env.clone(),
+            plants: control.plants.clone(),
+            environment: control.environment.clone(),
+            logs: control.logs.clone(),
+            snapshot: control.snapshot.clone(),
+            status: control.status.clone(),
+        }));
+        control
+    }
+
+    /// Sends a command; false once the runner has shut down
+    pub fn send(&self, command: ControlCommand) -> bool {
+        self.commands.send(command).is_ok()
+    }
 
-        *self.source_id.lock().unwrap() = Some(id);
+    pub fn start(&self) {
+        self.send(ControlCommand::Start);
     }
 
     pub fn stop(&self) {
-        let mut running = self.running.lock().unwrap();
-        if !*running {
-            return; // Already stopped
+        self.send(ControlCommand::Stop);
+    }
+
+    pub fn pause(&self) {
+        self.send(ControlCommand::Pause);
+    }
+
+    pub fn resume(&self) {
+        self.send(ControlCommand::Resume);
+    }
+
+    /// Pauses and advances exactly `n` steps
+    pub fn step(&self, n: u64) {
+        self.send(ControlCommand::Step(n));
+    }
+
+    /// Sets the timestep; the runner ignores non-positive values
+    pub fn set_dt(&self, dt: f32) {
+        self.send(ControlCommand::SetDt(dt));
+    }
+
+    pub fn set_speed(&self, speed: Speed) {
+        self.send(ControlCommand::SetSpeed(speed));
+    }
+
+    /// Jumps a replay to `step`
+    pub fn seek(&self, step: u64) {
+        self.send(ControlCommand::Seek(step));
+    }
+
+    pub fn reset(&self) {
+        self.send(ControlCommand::Reset);
+    }
+
+    /// Asks the runner to return from its loop
+    pub fn shutdown(&self) {
+        self.send(ControlCommand::Shutdown);
+    }
+
+    /// A new channel receiving every event from now on
+    pub fn subscribe(&self) -> Receiver<SimulationEvent> {
+        let (sender, receiver) = channel();
+        self.send(ControlCommand::Subscribe(Box::new(sender)));
+        receiver
+    }
+
+    /// Hands `observer` to the runner until `detach` is called with the returned id
+    pub fn attach_observer(&self, observer: Box<dyn SimulationObserver>) -> ObserverId {
+        let id = ObserverId::fresh();
+        self.send(ControlCommand::Attach(id, observer));
+        id
+    }
+
+    /// Has the runner drop an attached observer straight away
+    pub fn detach(&self, id: ObserverId) {
+        self.send(ControlCommand::Detach(id));
+    }
+
+    /// Runs `edit` on the runner's world between steps and waits for its result
+    pub fn edit<T: Send + 'static>(&self, edit: impl FnOnce(&mut SimulationEnv) -> T + Send + 'static) -> Option<T> {
+        let (reply, response) = channel();
+        let sent = self.send(ControlCommand::Edit(Box::new(move |env| {
+            let _ = reply.send(edit(env));
+        })));
+        if !sent {
+            return None;
         }
-        *running = false;
+        response.recv_timeout(REPLY_TIMEOUT).ok()
+    }
 
-        if let Some(source_id) = self.source_id.lock().unwrap().take() {
-            source_id.remove(); // Remove the idle_add source
+    /// Applies `mutation` at the next step boundary and waits for the runner's acknowledgement
+    pub fn mutate(&self, mutation: Mutation) -> Option<MutationResult> {
+        let (reply, response) = channel();
+        if !self.send(ControlCommand::Mutate(mutation, Some(reply))) {
+            return None;
         }
+        response.recv_timeout(REPLY_TIMEOUT).ok()
+    }
+
+    /// Checkpoint of the runner's world at the next step boundary
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        let (reply, response) = channel();
+        if !self.send(ControlCommand::Checkpoint(reply)) {
+            return None;
+        }
+        response.recv_timeout(REPLY_TIMEOUT).ok()
+    }
+
+    /// Continues from `checkpoint`, keeping the current run state
+    pub fn restore(&self, checkpoint: Checkpoint) {
+        self.send(ControlCommand::Restore(Box::new(checkpoint)));
     }
 
     pub fn plants(&self) -> Arc<Mutex<Vec<Plant>>> {
@@ -108,11 +203,41 @@ impl SimulationControl {
         self.logs.clone()
     }
 
-    pub fn engine(&self) -> Arc<Mutex<PlantEngine>> {
-        self.engine.clone()
+    /// Copy of the whole world as of the last step
+    pub fn env(&self) -> Arc<Mutex<SimulationEnv>> {
+        self.env.clone()
+    }
+
+    /// Latest C-Space snapshot, once the simulation has stepped
+    pub fn snapshot(&self) -> Arc<Mutex<Option<CSpaceSnapshot>>> {
+        self.snapshot.clone()
+    }
+
+    /// Last status the runner reported
+    pub fn status(&self) -> RunnerStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Species that can be planted in the current simulation
+    pub fn species(&self) -> Vec<Species> {
+        self.env.lock().unwrap().species.clone()
+    }
+
+    /// Switches how the metric is built from (E, H, D), from the next step on
+    pub fn set_metric(&self, metric: MetricModel) {
+        self.environment.lock().unwrap().metric = metric;
+        self.send(ControlCommand::Edit(Box::new(move |env| env.environment.metric = metric)));
+    }
+
+    /// Plants a founder of the named species at `pos`, returning its id
+    pub fn spawn_plant(&self, species: &str, pos: Vector3<f32>) -> Option<usize> {
+        match self.mutate(Mutation::AddPlant { species: species.to_string(), pos }) {
+            Some(Ok(MutationOutcome::PlantAdded(id))) => Some(id),
+            _ => None,
+        }
     }
 }
 
 pub mod prelude {
     pub use super::SimulationControl;
-}
\ No newline at end of file
+}
@@ -1,73 +1,364 @@
 // src/engines/cspace_engine.rs
-use nalgebra::Matrix3;
-use crate::plants::tropisms::{Plant, Environment};
+use std::borrow::Cow;
+use nalgebra::{Matrix3, Vector3};
+use crate::plants::tropisms::{Plant, Environment, Tropisms};
+use super::curvature::{curvature_at, CurvatureField, CurvatureSample};
+use super::geodesic::{GeodesicPath, MetricField};
+use super::observables::{self, Observable};
+use super::snapshot::{CSpaceSnapshot, FieldKind, PlantSnapshot, ScalarField};
+
+/// Width of the Gaussian kernel spreading each plant's ρ_c over space
+const DENSITY_SIGMA: f32 = 1.0;
+
+/// A point of interest for manifold distance queries
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Landmark {
+    Plant(usize), // By plant id
+    Light,
+    Water,
+    Obstacle(usize), // By index into `Environment::obstacles`
+    Point(Vector3<f32>),
+}
 
 pub struct CSpaceEngine {
     pub plants: Vec<Plant>,
     pub environment: Environment,
+    pub curvature_resolution: usize,          // Grid points per axis for `curvature`
+    pub curvature: CurvatureField,            // Sampled over the region occupied by the plants
+    pub plant_curvature: Vec<CurvatureSample>, // At each plant's own (E, H, D), in plant order
+    pub grid_cell_size: f32,                  // Spacing of `metric_field`
+    pub metric_field: MetricField,            // Induced metric over the space around plants and resources
+    pub time: f32,                            // Advanced by `update`
 }
 
 impl CSpaceEngine {
     pub fn new(plants: Vec<Plant>, environment: Environment) -> Self {
-        Self { plants, environment }
-    }
-
-    pub fn update(&mut self, dt: f32) {
-        for plant in &mut self.plants {
-            // Pass plant data directly instead of borrowing self
-            let rho_c = Self::compute_complex_density(plant);
-            let metric = Self::compute_metric_tensor(plant.energy, plant.distortion);
-            self.environment.metric_tensor = metric; // Update environment's metric tensor
-            println!(
-                "CSpace Update: Plant at {:?}, ρ_c={:.2}, metric={:?}",
-                plant.pos, rho_c, metric
-            );
+        Self {
+            plants,
+            environment,
+            curvature_resolution: 6,
+            curvature: CurvatureField::default(),
+            plant_curvature: Vec::new(),
+            grid_cell_size: 0.25,
+            metric_field: MetricField::default(),
+            time: 0.0,
+        }
+    }
+
+    /// Advances the manifold state by `dt` and returns what it looks like now
+    pub fn update(&mut self, dt: f32) -> CSpaceSnapshot {
+        if let Some(plant) = self.plants.last() {
+            self.environment.metric_tensor = self.metric_of(plant);
+        }
+        self.update_curvature();
+        self.metric_field = self.build_metric_field(&[]);
+        self.time += dt;
+        self.snapshot()
+    }
+
+    /// Per-plant metric invariants and every `FieldKind` for the current state
+    pub fn snapshot(&self) -> CSpaceSnapshot {
+        let plants = self
+            .plants
+            .iter()
+            .enumerate()
+            .map(|(i, plant)| {
+                let metric = self.metric_of(plant);
+                let mut eigenvalues = metric.symmetric_eigenvalues();
+                eigenvalues.as_mut_slice().sort_by(|a, b| a.total_cmp(b));
+                PlantSnapshot {
+                    id: plant.id,
+                    pos: plant.pos,
+                    rho_c: Tropisms::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy),
+                    metric,
+                    determinant: metric.determinant(),
+                    eigenvalues,
+                    scalar_curvature: self
+                        .plant_curvature
+                        .get(i)
+                        .map_or_else(|| self.curvature_at(&Self::coords_of(plant)).scalar, |c| c.scalar),
+                }
+            })
+            .collect();
+        let fields = FieldKind::ALL.iter().map(|&kind| (kind, self.scalar_field(kind))).collect();
+        CSpaceSnapshot { time: self.time, plants, fields, observables: self.observables() }
+    }
+
+    /// Entropy, metric volume of the occupied region and signal–growth mutual information
+    pub fn observables(&self) -> Vec<(Observable, f64)> {
+        let metric = |x: &Vector3<f64>| self.metric_at(x);
+        observables::measure(&self.plants, &self.environment, &metric, self.region(), self.curvature_resolution)
+    }
+
+    /// Samples a field over the x–y extent of `metric_field` (or the plants, before the first update)
+    pub fn scalar_field(&self, kind: FieldKind) -> ScalarField {
+        let field = if self.metric_field.tensors.is_empty() {
+            Cow::Owned(self.build_metric_field(&[]))
+        } else {
+            Cow::Borrowed(&self.metric_field)
+        };
+        let [width, height, _] = field.dims;
+        let env = &self.environment;
+        match kind {
+            FieldKind::ComplexDensity => {
+                let sources: Vec<(Vector3<f32>, f32)> = self
+                    .plants
+                    .iter()
+                    .map(|p| (p.pos, Tropisms::compute_complex_density(p.spatial_complexity, p.temporal_complexity, p.energy)))
+                    .collect();
+                ScalarField::sample(field.origin, field.cell_size, width, height, |x| {
+                    sources
+                        .iter()
+                        .map(|(pos, rho_c)| {
+                            let d = Vector3::new(x.x - pos.x, x.y - pos.y, 0.0);
+                            rho_c * (-d.norm_squared() / (2.0 * DENSITY_SIGMA * DENSITY_SIGMA)).exp()
+                        })
+                        .sum()
+                })
+            }
+            FieldKind::Energy => ScalarField::sample(field.origin, field.cell_size, width, height, |x| {
+                MetricField::local_state(env, x).x as f32
+            }),
+            FieldKind::Distortion => ScalarField::sample(field.origin, field.cell_size, width, height, |x| {
+                MetricField::local_state(env, x).z as f32
+            }),
+            FieldKind::MetricVolume => ScalarField::sample(field.origin, field.cell_size, width, height, |x| {
+                field.tensor_at(x).determinant().max(0.0).sqrt()
+            }),
+        }
+    }
+
+    /// Recomputes curvature on the grid and at every plant
+    pub fn update_curvature(&mut self) {
+        let (min, max) = self.region();
+        let n = self.curvature_resolution;
+        self.curvature = CurvatureField::sample(&|x: &Vector3<f64>| self.metric_at(x), min, max, [n, n, n]);
+        self.plant_curvature = self.plants.iter().map(|p| self.curvature_at(&Self::coords_of(p))).collect();
+    }
+
+    /// Curvature at an arbitrary point (E, H, D)
+    pub fn curvature_at(&self, coords: &Vector3<f64>) -> CurvatureSample {
+        curvature_at(&|x: &Vector3<f64>| self.metric_at(x), coords)
+    }
+
+    /// Manifold coordinates (E, H, D) of a plant
+    pub fn coords_of(plant: &Plant) -> Vector3<f64> {
+        Vector3::new(plant.energy as f64, plant.coherence as f64, plant.distortion as f64)
+    }
+
+    /// The metric as a function of manifold coordinates (E, H, D)
+    pub fn metric_at(&self, coords: &Vector3<f64>) -> Matrix3<f64> {
+        self.environment.metric.clamped_tensor(coords.x, coords.y, coords.z)
+    }
+
+    /// The metric at a plant's own (E, H, D)
+    pub fn metric_of(&self, plant: &Plant) -> Matrix3<f32> {
+        self.environment.metric.clamped_tensor(plant.energy, plant.coherence, plant.distortion)
+    }
+
+    /// Bounding box of the plants in (E, H, D), padded and kept away from the E = 0 and D = -ε poles
+    pub fn region(&self) -> (Vector3<f64>, Vector3<f64>) {
+        let mut coords = self.plants.iter().map(Self::coords_of);
+        let Some(first) = coords.next() else {
+            return (Vector3::new(1.0, -2.0, 0.01), Vector3::new(10.0, 2.0, 10.0));
+        };
+        let (min, max) = coords.fold((first, first), |(lo, hi), c| (lo.inf(&c), hi.sup(&c)));
+        let pad = (max - min) * 0.1 + Vector3::repeat(0.5);
+        let mut min = min - pad;
+        let max = max + pad;
+        min.x = min.x.max(0.1);
+        min.z = min.z.max(0.01);
+        (min, max.sup(&(min + Vector3::repeat(0.1))))
+    }
+
+    /// Physical position of a landmark
+    pub fn position(&self, landmark: Landmark) -> Option<Vector3<f32>> {
+        match landmark {
+            Landmark::Plant(id) => self.plants.iter().find(|p| p.id == id).map(|p| p.pos),
+            Landmark::Light => Some(self.environment.light_pos),
+            Landmark::Water => Some(self.environment.water_pos),
+            Landmark::Obstacle(i) => self.environment.obstacles.get(i).copied(),
+            Landmark::Point(pos) => Some(pos),
+        }
+    }
+
+    /// Induced metric over a box holding every plant (and its path), resource, obstacle and `extra` point
+    pub fn build_metric_field(&self, extra: &[Vector3<f32>]) -> MetricField {
+        let env = &self.environment;
+        let mut points: Vec<Vector3<f32>> = vec![env.light_pos, env.water_pos];
+        points.extend(env.obstacles.iter().copied());
+        points.extend(self.plants.iter().flat_map(|p| p.path.iter().copied().chain([p.pos])));
+        points.extend(extra.iter().copied());
+        let (min, max) = points
+            .iter()
+            .fold((points[0], points[0]), |(lo, hi), p| (lo.inf(p), hi.sup(p)));
+        let pad = Vector3::new(1.0, 1.0, 0.0);
+        MetricField::build(env, &|x: &Vector3<f64>| self.metric_at(x), min - pad, max + pad, self.grid_cell_size)
+    }
+
+    /// The cached field if it covers `points`, otherwise a fresh one that does
+    fn field_covering(&self, points: &[Vector3<f32>]) -> Cow<'_, MetricField> {
+        if points.iter().all(|p| self.metric_field.contains(p)) {
+            Cow::Borrowed(&self.metric_field)
+        } else {
+            Cow::Owned(self.build_metric_field(points))
         }
     }
 
-    fn compute_complex_density(plant: &Plant) -> f32 {
-        (plant.spatial_complexity.powi(2) + plant.temporal_complexity.powi(2)).sqrt() * plant.energy
+    /// Shortest path between two landmarks under the induced metric
+    pub fn shortest_path(&self, from: Landmark, to: Landmark) -> Option<GeodesicPath> {
+        let (a, b) = (self.position(from)?, self.position(to)?);
+        self.field_covering(&[a, b]).shortest_path(&a, &b)
+    }
+
+    /// Length of the shortest path between two landmarks: their distance in C-Space
+    pub fn geodesic_distance(&self, from: Landmark, to: Landmark) -> Option<f32> {
+        self.shortest_path(from, to).map(|p| p.length)
     }
 
-    fn compute_metric_tensor(energy: f32, distortion: f32) -> Matrix3<f32> {
-        let epsilon = 1e-6;
-        Matrix3::new(
-            1.0 / (energy * energy), 0.0, 0.0,
-            0.0, 1.0 / energy, 0.0,
-            0.0, 0.0, 1.0 / (distortion + epsilon),
-        )
+    /// Geodesic distance from where the plant started to where it is now, divided by the metric
+    /// length of the path it actually grew. 1.0 is an optimal path; None until it has moved.
+    pub fn navigation_efficiency(&self, plant_id: usize) -> Option<f32> {
+        let plant = self.plants.iter().find(|p| p.id == plant_id)?;
+        let start = *plant.path.first()?;
+        let mut grown = plant.path.clone();
+        grown.push(plant.pos);
+        let field = self.field_covering(&grown);
+        let actual = field.metric_length(&grown);
+        if actual <= f32::EPSILON {
+            return None;
+        }
+        let optimum = field.shortest_path(&start, &plant.pos)?.length;
+        Some(optimum / actual)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use nalgebra::Vector3;
+    use crate::plants::metric::{is_positive_definite, MetricFormulation, MetricModel};
+    use crate::plants::nutrients::NutrientField;
+    use crate::plants::species::Species;
+    use crate::simulation::scenario::Scenario;
+
+    /// The bundled default scene without its obstacle, on bare soil
+    fn environment() -> Environment {
+        let mut env = Scenario::example("default").unwrap().build().unwrap().environment;
+        env.obstacles.clear();
+        env.nutrients = NutrientField::default();
+        env
+    }
 
     #[test]
     fn test_cspace_engine_update() {
-        let plant = Plant {
-            pos: Vector3::new(0.0, 0.0, 0.0),
-            stem_dir: Vector3::new(0.0, 1.0, 0.0),
-            root_dir: Vector3::new(0.0, -1.0, 0.0),
-            energy: 10.0,
-            coherence: 1.0,
-            distortion: 0.0,
-            temporal_complexity: 0.0,
-            spatial_complexity: 0.0,
-        };
-        let env = Environment {
-            light_pos: Vector3::new(5.0, 5.0, 0.0),
-            water_pos: Vector3::new(2.0, -2.0, 0.0),
-            gravity: Vector3::new(0.0, -1.0, 0.0),
-            obstacles: vec![],
-            light_intensity: 1.0,
-            water_level: 1.0,
-            metric_tensor: Matrix3::identity(),
-            d_critical: 10.0,
-        };
-        let mut engine = CSpaceEngine::new(vec![plant], env);
+        let mut plant = Species::default().instantiate(0, Vector3::new(0.0, 0.0, 0.0));
+        plant.spatial_complexity = 1.0; // ρ_c = 10
+        let mut engine = CSpaceEngine::new(vec![plant], environment());
+        let snapshot = engine.update(1.0);
+        assert_eq!(snapshot.time, 1.0);
+        assert_eq!(snapshot.plants.len(), 1);
+        let p = &snapshot.plants[0];
+        // E = 10, D = 0: g = diag(0.01, 0.1, 1e6)
+        assert!((p.determinant - 0.01 * 0.1 * 1e6).abs() < 1.0);
+        assert!((p.eigenvalues.x - 0.01).abs() < 1e-5 && p.eigenvalues.z > 1e5);
+        assert!((p.scalar_curvature + 0.5).abs() < 1e-3);
+
+        // The density field peaks at the plant
+        assert_eq!(snapshot.fields.len(), FieldKind::ALL.len());
+        let density = snapshot.field(FieldKind::ComplexDensity).unwrap();
+        let (_, peak) = density.range();
+        let at_plant = (0..density.width)
+            .flat_map(|i| (0..density.height).map(move |j| (i, j)))
+            .min_by(|a, b| {
+                let da = density.cell_center(a.0, a.1).norm();
+                let db = density.cell_center(b.0, b.1).norm();
+                da.total_cmp(&db)
+            })
+            .and_then(|(i, j)| density.get(i, j))
+            .unwrap();
+        assert!(peak > 9.0 && (at_plant - peak).abs() < 1e-3 * peak);
+
+        // No obstacles: no distortion, energy at least 1, and the induced metric never shrinks space
+        let (d_min, d_max) = snapshot.field(FieldKind::Distortion).unwrap().range();
+        assert!(d_min == 0.0 && d_max == 0.0);
+        assert!(snapshot.field(FieldKind::Energy).unwrap().range().0 >= 1.0);
+        assert!(snapshot.field(FieldKind::MetricVolume).unwrap().range().0 >= 1.0 - 1e-4);
+        assert_eq!(snapshot.observable(Observable::StateEntropy), Some(0.0));
+        assert!(snapshot.observable(Observable::MetricVolume).unwrap() > 0.0);
+        assert_eq!(engine.curvature.samples.len(), 6 * 6 * 6);
+        assert_eq!(engine.plant_curvature.len(), 1);
+    }
+
+    #[test]
+    fn test_geodesic_queries() {
+        let mut straight = Species::default().instantiate(0, Vector3::zeros());
+        let mut zigzag = Species::default().instantiate(1, Vector3::zeros());
+        for i in 1..=20 {
+            straight.pos = Vector3::new(i as f32 * 0.1, 0.0, 0.0);
+            straight.record_path();
+            zigzag.pos = Vector3::new(i as f32 * 0.1, if i % 2 == 0 { 0.0 } else { 0.4 }, 0.0);
+            zigzag.record_path();
+        }
+        let mut engine = CSpaceEngine::new(vec![straight, zigzag], environment());
         engine.update(1.0);
-        // Check console output manually for now; add assertions later as logic expands
+
+        // The induced metric is never shorter than Euclidean distance
+        let euclidean = (engine.environment.light_pos - engine.environment.water_pos).norm();
+        assert!(engine.geodesic_distance(Landmark::Light, Landmark::Water).unwrap() >= euclidean * 0.99);
+
+        let efficient = engine.navigation_efficiency(0).unwrap();
+        let wasteful = engine.navigation_efficiency(1).unwrap();
+        assert!(efficient > 0.9, "{}", efficient);
+        assert!(wasteful < 0.6 * efficient, "{} vs {}", wasteful, efficient);
+        assert!(engine.navigation_efficiency(7).is_none());
+    }
+
+    #[test]
+    fn test_metric_curvature() {
+        // dE²/E² + dH²/E is a hyperbolic plane with K = -1/4; D adds a flat direction, so R = 2K
+        let engine = CSpaceEngine::new(Vec::new(), environment());
+        let sample = engine.curvature_at(&Vector3::new(5.0, 0.3, 2.0));
+        assert!((sample.scalar + 0.5).abs() < 1e-4, "{}", sample.scalar);
+        assert!((sample.ricci[(0, 0)] + 0.25 / 25.0).abs() < 1e-5);
+        assert!(sample.ricci[(2, 2)].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_metric_shared_with_tropisms() {
+        for formulation in MetricFormulation::ALL {
+            let mut env = environment();
+            env.metric = MetricModel::new(formulation, 0.4).unwrap();
+            let mut plant = Species::default().instantiate(0, Vector3::zeros());
+            plant.coherence = 0.7;
+            plant.distortion = 2.0;
+            Tropisms::apply_all(&mut plant, &mut env, 0.1, &mut rand::thread_rng());
+
+            // Tropisms leave g in the environment; the engine computes the same tensor from the same state
+            let engine = CSpaceEngine::new(vec![plant.clone()], env.clone());
+            assert_eq!(engine.metric_of(&plant), env.metric_tensor, "{}", formulation);
+            assert_eq!(engine.snapshot().plants[0].metric, env.metric_tensor);
+            let in_f64 = engine.metric_at(&CSpaceEngine::coords_of(&plant)).map(|v| v as f32);
+            assert!((in_f64 - env.metric_tensor).norm() < 1e-5 * env.metric_tensor.norm());
+        }
+    }
+
+    #[test]
+    fn test_starved_plant_metric_stays_positive_definite() {
+        for formulation in MetricFormulation::ALL {
+            let mut env = environment();
+            env.metric = MetricModel::new(formulation, 0.4).unwrap();
+            let mut plant = Species::default().instantiate(0, Vector3::zeros());
+            plant.genotype.gravitropism_cost = 1000.0;
+            plant.coherence = 0.7;
+            while plant.energy > 0.0 {
+                Tropisms::apply_all(&mut plant, &mut env, 0.1, &mut rand::thread_rng());
+            }
+            assert!(is_positive_definite(&env.metric_tensor), "{}: {}", formulation, env.metric_tensor);
+
+            let mut engine = CSpaceEngine::new(vec![plant], env);
+            let metric = engine.update(0.1).plants[0].metric;
+            assert!(is_positive_definite(&metric), "{}: {}", formulation, metric);
+            assert!(is_positive_definite(&engine.environment.metric_tensor));
+        }
     }
-}
\ No newline at end of file
+}
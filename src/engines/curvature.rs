@@ -0,0 +1,216 @@
+// src/engines/curvature.rs
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use nalgebra::{Matrix3, Vector3};
+
+/// Γ[k][i][j] = Γ^k_ij
+pub type Christoffel = [[[f64; 3]; 3]; 3];
+/// R[a][b][c][d] = R^a_bcd
+pub type Riemann = [[[[f64; 3]; 3]; 3]; 3];
+
+/// Geometric quantities of the metric at one point of the manifold (coordinates E, H, D)
+#[derive(Debug, Clone)]
+pub struct CurvatureSample {
+    pub coords: Vector3<f64>,
+    pub metric: Matrix3<f64>,
+    pub christoffel: Christoffel,
+    pub riemann: Riemann,
+    pub ricci: Matrix3<f64>,
+    pub scalar: f64,       // Ricci scalar R
+    pub kretschmann: f64,  // R_abcd R^abcd: non-zero wherever the manifold is curved, whatever the sign
+}
+
+/// Curvature sampled on a regular grid over a box of the manifold
+#[derive(Debug, Clone, Default)]
+pub struct CurvatureField {
+    pub min: Vector3<f64>,
+    pub max: Vector3<f64>,
+    pub resolution: [usize; 3],
+    pub samples: Vec<CurvatureSample>, // Index: (i * ny + j) * nz + k
+}
+
+impl CurvatureField {
+    /// Samples `metric` at `resolution` points per axis between `min` and `max` (inclusive)
+    pub fn sample<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(
+        metric: &F,
+        min: Vector3<f64>,
+        max: Vector3<f64>,
+        resolution: [usize; 3],
+    ) -> Self {
+        let axis = |d: usize, i: usize| {
+            if resolution[d] <= 1 {
+                (min[d] + max[d]) / 2.0
+            } else {
+                min[d] + (max[d] - min[d]) * i as f64 / (resolution[d] - 1) as f64
+            }
+        };
+        let mut samples = Vec::with_capacity(resolution.iter().product());
+        for i in 0..resolution[0] {
+            for j in 0..resolution[1] {
+                for k in 0..resolution[2] {
+                    let coords = Vector3::new(axis(0, i), axis(1, j), axis(2, k));
+                    samples.push(curvature_at(metric, &coords));
+                }
+            }
+        }
+        Self { min, max, resolution, samples }
+    }
+
+    pub fn get(&self, i: usize, j: usize, k: usize) -> Option<&CurvatureSample> {
+        let [nx, ny, nz] = self.resolution;
+        if i >= nx || j >= ny || k >= nz {
+            return None;
+        }
+        self.samples.get((i * ny + j) * nz + k)
+    }
+
+    /// Sample with the largest Kretschmann invariant
+    pub fn most_curved(&self) -> Option<&CurvatureSample> {
+        self.samples.iter().max_by(|a, b| a.kretschmann.total_cmp(&b.kretschmann))
+    }
+
+    /// Range of the Ricci scalar over the grid
+    pub fn scalar_range(&self) -> (f64, f64) {
+        self.samples.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), s| {
+            (lo.min(s.scalar), hi.max(s.scalar))
+        })
+    }
+}
+
+/// Finite-difference step for coordinate `x`
+fn step(x: f64) -> f64 {
+    1e-4 * x.abs().max(1.0)
+}
+
+/// Christoffel symbols of the second kind, Γ^k_ij = ½ g^kl (∂_i g_lj + ∂_j g_li − ∂_l g_ij),
+/// with metric derivatives by central differences
+pub fn christoffel<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(metric: &F, x: &Vector3<f64>) -> Christoffel {
+    let g_inv = metric(x).try_inverse().unwrap_or_else(Matrix3::zeros);
+    let mut dg = [Matrix3::zeros(); 3]; // dg[l] = ∂_l g
+    for (l, d) in dg.iter_mut().enumerate() {
+        let h = step(x[l]);
+        let mut forward = *x;
+        let mut backward = *x;
+        forward[l] += h;
+        backward[l] -= h;
+        *d = (metric(&forward) - metric(&backward)) / (2.0 * h);
+    }
+
+    let mut gamma = [[[0.0; 3]; 3]; 3];
+    for (k, gamma_k) in gamma.iter_mut().enumerate() {
+        for i in 0..3 {
+            for j in 0..3 {
+                gamma_k[i][j] = (0..3)
+                    .map(|l| 0.5 * g_inv[(k, l)] * (dg[i][(l, j)] + dg[j][(l, i)] - dg[l][(i, j)]))
+                    .sum();
+            }
+        }
+    }
+    gamma
+}
+
+/// Every curvature quantity of `metric` at `x`
+pub fn curvature_at<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(metric: &F, x: &Vector3<f64>) -> CurvatureSample {
+    let g = metric(x);
+    let g_inv = g.try_inverse().unwrap_or_else(Matrix3::zeros);
+    let gamma = christoffel(metric, x);
+
+    // dgamma[m] = ∂_m Γ
+    let mut dgamma = [[[[0.0; 3]; 3]; 3]; 3];
+    for (m, d) in dgamma.iter_mut().enumerate() {
+        let h = step(x[m]);
+        let mut forward = *x;
+        let mut backward = *x;
+        forward[m] += h;
+        backward[m] -= h;
+        let (gf, gb) = (christoffel(metric, &forward), christoffel(metric, &backward));
+        for a in 0..3 {
+            for b in 0..3 {
+                for c in 0..3 {
+                    d[a][b][c] = (gf[a][b][c] - gb[a][b][c]) / (2.0 * h);
+                }
+            }
+        }
+    }
+
+    // R^a_bcd = ∂_c Γ^a_db − ∂_d Γ^a_cb + Γ^a_ce Γ^e_db − Γ^a_de Γ^e_cb
+    let mut riemann = [[[[0.0; 3]; 3]; 3]; 3];
+    for (a, r_a) in riemann.iter_mut().enumerate() {
+        for (b, r_ab) in r_a.iter_mut().enumerate() {
+            for (c, r_abc) in r_ab.iter_mut().enumerate() {
+                for (d, r) in r_abc.iter_mut().enumerate() {
+                    *r = dgamma[c][a][d][b] - dgamma[d][a][c][b]
+                        + (0..3)
+                            .map(|e| gamma[a][c][e] * gamma[e][d][b] - gamma[a][d][e] * gamma[e][c][b])
+                            .sum::<f64>();
+                }
+            }
+        }
+    }
+
+    // R_bd = R^a_bad, R = g^bd R_bd
+    let ricci = Matrix3::from_fn(|b, d| (0..3).map(|a| riemann[a][b][a][d]).sum());
+    let scalar = (0..3).flat_map(|b| (0..3).map(move |d| (b, d))).map(|(b, d)| g_inv[(b, d)] * ricci[(b, d)]).sum();
+
+    // Kretschmann: lower the first index, raise the last three
+    let mut kretschmann = 0.0;
+    for a in 0..3 {
+        for b in 0..3 {
+            for c in 0..3 {
+                for d in 0..3 {
+                    let lower: f64 = (0..3).map(|e| g[(a, e)] * riemann[e][b][c][d]).sum();
+                    let mut upper = 0.0;
+                    for p in 0..3 {
+                        for q in 0..3 {
+                            for r in 0..3 {
+                                upper += g_inv[(b, p)] * g_inv[(c, q)] * g_inv[(d, r)] * riemann[a][p][q][r];
+                            }
+                        }
+                    }
+                    kretschmann += lower * upper;
+                }
+            }
+        }
+    }
+
+    CurvatureSample { coords: *x, metric: g, christoffel: gamma, riemann, ricci, scalar, kretschmann }
+}
+
+/// Writes one row per grid sample: coordinates, Ricci scalar, Kretschmann invariant and Ricci components
+pub fn write_curvature_csv(path: &Path, field: &CurvatureField) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "energy,coherence,distortion,scalar,kretschmann,ricci_ee,ricci_hh,ricci_dd,ricci_eh,ricci_ed,ricci_hd")?;
+    for s in &field.samples {
+        let r = &s.ricci;
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            s.coords.x, s.coords.y, s.coords.z, s.scalar, s.kretschmann,
+            r[(0, 0)], r[(1, 1)], r[(2, 2)], r[(0, 1)], r[(0, 2)], r[(1, 2)]
+        )?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_metric_has_no_curvature() {
+        let sample = curvature_at(&|_: &Vector3<f64>| Matrix3::identity(), &Vector3::new(1.0, 2.0, 3.0));
+        assert!(sample.scalar.abs() < 1e-9);
+        assert!(sample.christoffel.iter().flatten().flatten().all(|g| g.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_sphere_curvature() {
+        // Unit 2-sphere (θ, φ) times a flat line: R = 2, Kretschmann = 4
+        let sphere = |x: &Vector3<f64>| Matrix3::new(1.0, 0.0, 0.0, 0.0, x.x.sin().powi(2), 0.0, 0.0, 0.0, 1.0);
+        let sample = curvature_at(&sphere, &Vector3::new(1.0, 0.5, 0.0));
+        assert!((sample.scalar - 2.0).abs() < 1e-4, "{}", sample.scalar);
+        assert!((sample.kretschmann - 4.0).abs() < 1e-3, "{}", sample.kretschmann);
+        assert!((sample.christoffel[0][1][1] + 1.0f64.sin() * 1.0f64.cos()).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,283 @@
+// src/engines/geodesic.rs
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use nalgebra::{Matrix3, Vector3};
+use crate::plants::tropisms::Environment;
+
+/// Width of an obstacle's distortion bump; thigmotropism reacts within 1.0
+const OBSTACLE_SIGMA: f32 = 0.5;
+
+/// Shortest path through a metric field and its metric length
+#[derive(Debug, Clone)]
+pub struct GeodesicPath {
+    pub points: Vec<Vector3<f32>>,
+    pub length: f32,
+}
+
+/// Riemannian metric on a regular grid over physical space. Each cell holds the metric induced by
+/// mapping space into C-Space: G = I + Jᵀ g J, where J is the Jacobian of x ↦ (E(x), H, D(x)).
+/// Moving where energy or distortion change quickly is therefore expensive.
+#[derive(Debug, Clone, Default)]
+pub struct MetricField {
+    pub origin: Vector3<f32>, // Center of cell (0, 0, 0)
+    pub cell_size: f32,
+    pub dims: [usize; 3],
+    pub tensors: Vec<Matrix3<f32>>, // Index: (k * ny + j) * nx + i
+}
+
+impl MetricField {
+    /// Local C-Space state (E, H, D) at a point: energy from light, water and soil nutrients,
+    /// distortion from nearby obstacles. H has no spatial field and is held at 0.
+    pub fn local_state(env: &Environment, pos: &Vector3<f32>) -> Vector3<f64> {
+        let energy = 1.0
+            + env.light_intensity / (1.0 + (env.light_pos - pos).norm())
+            + env.water_level / (1.0 + (env.water_pos - pos).norm())
+            + env.nutrients.sample(*pos).total();
+        let distortion: f32 = env
+            .obstacles
+            .iter()
+            .map(|o| env.d_critical * (-(o - pos).norm_squared() / (2.0 * OBSTACLE_SIGMA * OBSTACLE_SIGMA)).exp())
+            .sum();
+        Vector3::new(energy as f64, 0.0, distortion as f64)
+    }
+
+    /// Pullback of `metric` (a function of (E, H, D)) to physical space at `pos`
+    pub fn induced_metric<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(env: &Environment, metric: &F, pos: &Vector3<f32>) -> Matrix3<f32> {
+        let state = Self::local_state(env, pos);
+        let h = 1e-3;
+        let mut jacobian = Matrix3::<f64>::zeros(); // Rows: E, H, D; columns: x, y, z
+        for axis in 0..3 {
+            let mut forward = *pos;
+            let mut backward = *pos;
+            forward[axis] += h;
+            backward[axis] -= h;
+            let derivative = (Self::local_state(env, &forward) - Self::local_state(env, &backward)) / (2.0 * h as f64);
+            jacobian.set_column(axis, &derivative);
+        }
+        let induced = Matrix3::identity() + jacobian.transpose() * metric(&state) * jacobian;
+        induced.map(|v| v as f32)
+    }
+
+    /// Samples the induced metric over [min, max] (padded by one cell). A flat box gets a single z layer.
+    pub fn build<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(
+        env: &Environment,
+        metric: &F,
+        min: Vector3<f32>,
+        max: Vector3<f32>,
+        cell_size: f32,
+    ) -> Self {
+        let origin = min - Vector3::repeat(cell_size);
+        let extent = max - min + Vector3::repeat(2.0 * cell_size);
+        let count = |e: f32| (e / cell_size).ceil() as usize + 1;
+        let dims = [
+            count(extent.x),
+            count(extent.y),
+            if max.z - min.z < cell_size { 1 } else { count(extent.z) },
+        ];
+        let origin = if dims[2] == 1 { Vector3::new(origin.x, origin.y, (min.z + max.z) / 2.0) } else { origin };
+
+        let mut field = Self { origin, cell_size, dims, tensors: Vec::with_capacity(dims.iter().product()) };
+        for k in 0..dims[2] {
+            for j in 0..dims[1] {
+                for i in 0..dims[0] {
+                    let center = field.cell_center([i, j, k]);
+                    field.tensors.push(Self::induced_metric(env, metric, &center));
+                }
+            }
+        }
+        field
+    }
+
+    fn index(&self, cell: [usize; 3]) -> usize {
+        (cell[2] * self.dims[1] + cell[1]) * self.dims[0] + cell[0]
+    }
+
+    fn cell_at(&self, index: usize) -> [usize; 3] {
+        let [nx, ny, _] = self.dims;
+        [index % nx, (index / nx) % ny, index / (nx * ny)]
+    }
+
+    pub fn cell_center(&self, cell: [usize; 3]) -> Vector3<f32> {
+        self.origin + Vector3::new(cell[0] as f32, cell[1] as f32, cell[2] as f32) * self.cell_size
+    }
+
+    /// Nearest cell to `pos`, if it lies inside the field
+    pub fn cell_of(&self, pos: &Vector3<f32>) -> Option<[usize; 3]> {
+        let mut cell = [0; 3];
+        for axis in 0..3 {
+            if self.dims[axis] == 1 {
+                continue;
+            }
+            let t = ((pos[axis] - self.origin[axis]) / self.cell_size).round();
+            if t < 0.0 || t as usize >= self.dims[axis] {
+                return None;
+            }
+            cell[axis] = t as usize;
+        }
+        (self.dims.iter().all(|&d| d > 0)).then_some(cell)
+    }
+
+    pub fn contains(&self, pos: &Vector3<f32>) -> bool {
+        self.cell_of(pos).is_some()
+    }
+
+    /// Metric tensor of the cell nearest `pos` (identity outside the field)
+    pub fn tensor_at(&self, pos: &Vector3<f32>) -> Matrix3<f32> {
+        self.cell_of(pos).map_or_else(Matrix3::identity, |c| self.tensors[self.index(c)])
+    }
+
+    /// Length of a polyline under the metric, evaluating the metric at each segment's midpoint
+    pub fn metric_length(&self, points: &[Vector3<f32>]) -> f32 {
+        points
+            .windows(2)
+            .map(|w| {
+                let d = w[1] - w[0];
+                let g = self.tensor_at(&((w[0] + w[1]) / 2.0));
+                (d.transpose() * g * d)[(0, 0)].max(0.0).sqrt()
+            })
+            .sum()
+    }
+
+    /// Dijkstra over the grid (8 neighbours in a flat field, 26 otherwise). Edge costs use the mean
+    /// metric of the two cells. Returns None if either end lies outside the field.
+    pub fn shortest_path(&self, from: &Vector3<f32>, to: &Vector3<f32>) -> Option<GeodesicPath> {
+        let start = self.index(self.cell_of(from)?);
+        let goal = self.index(self.cell_of(to)?);
+
+        let mut offsets = Vec::with_capacity(26);
+        let z_range = if self.dims[2] == 1 { 0..=0 } else { -1..=1 };
+        for dz in z_range {
+            for dy in -1i64..=1 {
+                for dx in -1i64..=1 {
+                    if (dx, dy, dz) != (0, 0, 0) {
+                        offsets.push([dx, dy, dz]);
+                    }
+                }
+            }
+        }
+
+        let mut cost = vec![f32::INFINITY; self.tensors.len()];
+        let mut previous = vec![usize::MAX; self.tensors.len()];
+        let mut queue = BinaryHeap::new();
+        cost[start] = 0.0;
+        queue.push(Visit { cost: 0.0, index: start });
+        while let Some(Visit { cost: c, index }) = queue.pop() {
+            if index == goal {
+                break;
+            }
+            if c > cost[index] {
+                continue;
+            }
+            let cell = self.cell_at(index);
+            for offset in &offsets {
+                let mut next = [0; 3];
+                let mut inside = true;
+                for axis in 0..3 {
+                    let v = cell[axis] as i64 + offset[axis];
+                    inside &= v >= 0 && (v as usize) < self.dims[axis];
+                    next[axis] = v.max(0) as usize;
+                }
+                if !inside {
+                    continue;
+                }
+                let n = self.index(next);
+                let d = Vector3::new(offset[0] as f32, offset[1] as f32, offset[2] as f32) * self.cell_size;
+                let g = (self.tensors[index] + self.tensors[n]) * 0.5;
+                let step = (d.transpose() * g * d)[(0, 0)].max(0.0).sqrt();
+                if c + step < cost[n] {
+                    cost[n] = c + step;
+                    previous[n] = index;
+                    queue.push(Visit { cost: c + step, index: n });
+                }
+            }
+        }
+        if !cost[goal].is_finite() {
+            return None;
+        }
+
+        let mut cells = vec![goal];
+        while let Some(&last) = cells.last() {
+            if last == start {
+                break;
+            }
+            cells.push(previous[last]);
+        }
+        // Exact end points replace the start and goal cell centers
+        let mut points = vec![*from];
+        if cells.len() > 2 {
+            points.extend(cells[1..cells.len() - 1].iter().rev().map(|&i| self.cell_center(self.cell_at(i))));
+        }
+        points.push(*to);
+        let length = self.metric_length(&points);
+        Some(GeodesicPath { points, length })
+    }
+}
+
+/// Priority-queue entry ordered so the cheapest visit pops first
+#[derive(Debug, Clone, Copy)]
+struct Visit {
+    cost: f32,
+    index: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost).then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plants::nutrients::NutrientField;
+    use crate::simulation::scenario::Scenario;
+
+    /// The bundled default scene with `obstacles` instead of its own, without light, water or nutrients
+    fn environment(obstacles: Vec<Vector3<f32>>) -> Environment {
+        let mut env = Scenario::example("default").unwrap().build().unwrap().environment;
+        env.obstacles = obstacles;
+        env.light_intensity = 0.0;
+        env.water_level = 0.0;
+        env.nutrients = NutrientField::default();
+        env
+    }
+
+    fn build(env: &Environment) -> MetricField {
+        let metric = |x: &Vector3<f64>| Matrix3::from_diagonal(&Vector3::new(1.0 / (x.x * x.x), 1.0 / x.x, 1.0 / (x.z + 1e-6)));
+        MetricField::build(env, &metric, Vector3::new(-3.0, -3.0, 0.0), Vector3::new(3.0, 3.0, 0.0), 0.1)
+    }
+
+    #[test]
+    fn test_uniform_field_is_euclidean() {
+        let field = build(&environment(Vec::new()));
+        assert_eq!(field.dims[2], 1);
+        let path = field.shortest_path(&Vector3::new(-2.0, 0.0, 0.0), &Vector3::new(2.0, 0.0, 0.0)).unwrap();
+        assert!((path.length - 4.0).abs() < 1e-3, "{}", path.length);
+        assert!(field.shortest_path(&Vector3::new(-9.0, 0.0, 0.0), &Vector3::zeros()).is_none());
+    }
+
+    #[test]
+    fn test_path_bends_around_obstacle() {
+        let field = build(&environment(vec![Vector3::zeros()]));
+        let (from, to) = (Vector3::new(-2.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0));
+        let path = field.shortest_path(&from, &to).unwrap();
+        // Cheaper than the straight line through the distortion bump, and it leaves the x axis
+        let straight: Vec<Vector3<f32>> = (0..=40).map(|i| from + (to - from) * (i as f32 / 40.0)).collect();
+        assert!(path.length < field.metric_length(&straight));
+        assert!(path.points.iter().any(|p| p.y.abs() > 0.5));
+    }
+}
@@ -1,2 +1,6 @@
 pub mod cspace_engine;
-pub mod plant_engine;
\ No newline at end of file
+pub mod curvature;
+pub mod geodesic;
+pub mod observables;
+pub mod plant_engine;
+pub mod snapshot;
\ No newline at end of file
@@ -0,0 +1,189 @@
+// src/engines/observables.rs
+use std::collections::HashMap;
+use std::fmt;
+use nalgebra::{Matrix3, Vector3};
+use crate::plants::tropisms::{Environment, Plant};
+
+/// Bins per axis of the (E, H, D) histogram behind `StateEntropy`
+pub const ENTROPY_BINS: usize = 4;
+/// Angular sectors in the x–y plane used to discretize directions for mutual information
+pub const DIRECTION_SECTORS: usize = 8;
+
+/// Population-level quantities measured each step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Observable {
+    StateEntropy,     // Shannon entropy (bits) of the plants' (E, H, D) states
+    MetricVolume,     // ∫ √det g dE dH dD over the region the plants occupy
+    LightInformation, // Mutual information (bits) between the direction to light and stem direction
+    WaterInformation, // Mutual information (bits) between the direction to water and root direction
+}
+
+impl Observable {
+    pub const ALL: [Observable; 4] = [
+        Observable::StateEntropy,
+        Observable::MetricVolume,
+        Observable::LightInformation,
+        Observable::WaterInformation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Observable::StateEntropy => "state_entropy",
+            Observable::MetricVolume => "metric_volume",
+            Observable::LightInformation => "light_information",
+            Observable::WaterInformation => "water_information",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|o| o.name() == name)
+    }
+}
+
+impl fmt::Display for Observable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Every observable for `plants` in `env`. `metric` is g on (E, H, D) and (`min`, `max`) the
+/// occupied box, integrated with `resolution` midpoints per axis.
+pub fn measure<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(
+    plants: &[Plant],
+    env: &Environment,
+    metric: &F,
+    (min, max): (Vector3<f64>, Vector3<f64>),
+    resolution: usize,
+) -> Vec<(Observable, f64)> {
+    let states: Vec<Vector3<f64>> = plants
+        .iter()
+        .map(|p| Vector3::new(p.energy as f64, p.coherence as f64, p.distortion as f64))
+        .collect();
+    let light: Vec<(usize, usize)> = plants
+        .iter()
+        .filter_map(|p| Some((sector(&(env.light_pos - p.pos))?, sector(&p.stem_dir)?)))
+        .collect();
+    let water: Vec<(usize, usize)> = plants
+        .iter()
+        .filter_map(|p| Some((sector(&(env.water_pos - p.pos))?, sector(&p.root_dir)?)))
+        .collect();
+    Observable::ALL
+        .iter()
+        .map(|&o| {
+            let value = match o {
+                Observable::StateEntropy => state_entropy(&states, ENTROPY_BINS),
+                Observable::MetricVolume => metric_volume(metric, min, max, resolution),
+                Observable::LightInformation => mutual_information(&light),
+                Observable::WaterInformation => mutual_information(&water),
+            };
+            (o, value)
+        })
+        .collect()
+}
+
+/// Entropy in bits of a histogram with `bins` per axis spanning the population's range
+pub fn state_entropy(states: &[Vector3<f64>], bins: usize) -> f64 {
+    let Some(first) = states.first() else { return 0.0 };
+    let (min, max) = states.iter().fold((*first, *first), |(lo, hi), s| (lo.inf(s), hi.sup(s)));
+    let bin = |v: f64, axis: usize| {
+        let span = max[axis] - min[axis];
+        if span <= 0.0 || !v.is_finite() {
+            0
+        } else {
+            (((v - min[axis]) / span * bins as f64) as usize).min(bins - 1)
+        }
+    };
+    let mut counts: HashMap<[usize; 3], usize> = HashMap::new();
+    for s in states {
+        *counts.entry([bin(s.x, 0), bin(s.y, 1), bin(s.z, 2)]).or_default() += 1;
+    }
+    entropy(counts.values().copied(), states.len())
+}
+
+/// ∫ √det g over the box [min, max] by the midpoint rule; non-positive determinants count as 0
+pub fn metric_volume<F: Fn(&Vector3<f64>) -> Matrix3<f64>>(metric: &F, min: Vector3<f64>, max: Vector3<f64>, resolution: usize) -> f64 {
+    let n = resolution.max(1);
+    let cell = (max - min) / n as f64;
+    let mut total = 0.0;
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..n {
+                let x = min + cell.component_mul(&Vector3::new(i as f64 + 0.5, j as f64 + 0.5, k as f64 + 0.5));
+                let det = metric(&x).determinant();
+                if det.is_finite() && det > 0.0 {
+                    total += det.sqrt();
+                }
+            }
+        }
+    }
+    total * cell.x * cell.y * cell.z
+}
+
+/// Plug-in estimate (bits) of I(X; Y) from paired discrete samples
+pub fn mutual_information(pairs: &[(usize, usize)]) -> f64 {
+    let n = pairs.len();
+    if n == 0 {
+        return 0.0;
+    }
+    let mut joint: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut xs: HashMap<usize, usize> = HashMap::new();
+    let mut ys: HashMap<usize, usize> = HashMap::new();
+    for &(x, y) in pairs {
+        *joint.entry((x, y)).or_default() += 1;
+        *xs.entry(x).or_default() += 1;
+        *ys.entry(y).or_default() += 1;
+    }
+    // I = H(X) + H(Y) − H(X, Y); clamp rounding below zero
+    let h = |counts: Vec<usize>| entropy(counts.into_iter(), n);
+    (h(xs.into_values().collect()) + h(ys.into_values().collect()) - h(joint.into_values().collect())).max(0.0)
+}
+
+/// Sector of a direction's angle in the x–y plane, or None for a vertical or zero vector
+pub fn sector(v: &Vector3<f32>) -> Option<usize> {
+    if v.x.hypot(v.y) <= f32::EPSILON {
+        return None;
+    }
+    let turn = (v.y.atan2(v.x) / std::f32::consts::TAU).rem_euclid(1.0);
+    Some(((turn * DIRECTION_SECTORS as f32) as usize).min(DIRECTION_SECTORS - 1))
+}
+
+fn entropy<I: Iterator<Item = usize>>(counts: I, total: usize) -> f64 {
+    counts
+        .filter(|&c| c > 0)
+        .map(|c| {
+            let p = c as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_and_mutual_information() {
+        let same = vec![Vector3::new(1.0, 0.5, 0.0); 8];
+        assert_eq!(state_entropy(&same, ENTROPY_BINS), 0.0);
+        let spread: Vec<Vector3<f64>> = (0..4).map(|i| Vector3::new(i as f64, 0.0, 0.0)).collect();
+        assert!((state_entropy(&spread, ENTROPY_BINS) - 2.0).abs() < 1e-12);
+
+        // Growth that copies the signal carries all of its 2 bits; growth that ignores it carries none
+        let aligned: Vec<(usize, usize)> = (0..40).map(|i| (i % 4, i % 4)).collect();
+        let ignored: Vec<(usize, usize)> = (0..40).map(|i| (i % 4, 0)).collect();
+        assert!((mutual_information(&aligned) - 2.0).abs() < 1e-12);
+        assert!(mutual_information(&ignored).abs() < 1e-12);
+        assert_eq!(sector(&Vector3::new(1.0, 0.1, 0.0)), Some(0));
+        assert_eq!(sector(&Vector3::new(0.0, -1.0, 0.0)), Some(6));
+        assert_eq!(sector(&Vector3::new(0.0, 0.0, 1.0)), None);
+    }
+
+    #[test]
+    fn test_metric_volume() {
+        let (min, max) = (Vector3::new(1.0, 0.0, 0.0), Vector3::new(3.0, 2.0, 1.0));
+        assert!((metric_volume(&|_: &Vector3<f64>| Matrix3::identity(), min, max, 4) - 4.0).abs() < 1e-12);
+        // √det diag(1/E², 1, 1) = 1/E, so the volume is 2·1·ln 3
+        let scaled = |x: &Vector3<f64>| Matrix3::from_diagonal(&Vector3::new(1.0 / (x.x * x.x), 1.0, 1.0));
+        assert!((metric_volume(&scaled, min, max, 64) - 2.0 * 3.0f64.ln()).abs() < 1e-3);
+    }
+}
@@ -1,4 +1,6 @@
 // src/engines/plant_engine.rs
+use nalgebra::Vector3;
+use rand::Rng;
 use crate::simulation::simulation_env::SimulationEnv;
 use crate::plants::tropisms::{Tropisms, TropismResult};
 
@@ -14,17 +16,52 @@ impl PlantEngine {
     pub fn update(&mut self, dt: f32) -> Vec<Vec<TropismResult>> {
         let mut results = Vec::new();
         for plant in &mut self.env.plants {
-            let plant_results = Tropisms::apply_all(plant, &mut self.env.environment, dt);
+            let mut plant_results = Tropisms::apply_all(plant, &mut self.env.environment, dt, &mut self.env.rng);
+            for result in &mut plant_results {
+                result.step = self.env.step;
+            }
             results.push(plant_results);
+            plant.record_path();
+            plant.age += dt;
         }
+        self.update_lifecycle();
         self.env.update_time(dt);
         results
     }
+
+    /// Sets seeds for mature plants and removes plants that have outlived their lifespan
+    fn update_lifecycle(&mut self) {
+        let env = &mut self.env;
+        env.plants.retain(|p| p.age < p.genotype.lifespan);
+
+        let capacity = env.max_plants.saturating_sub(env.plants.len());
+        let mut seedlings = Vec::new();
+        for plant in &mut env.plants {
+            if seedlings.len() >= capacity {
+                break;
+            }
+            if !plant.can_seed() {
+                continue;
+            }
+            let variation = env
+                .species
+                .iter()
+                .find(|s| s.name == plant.species)
+                .map(|s| s.variation)
+                .unwrap_or_default();
+            let offset = Vector3::new(env.rng.gen_range(-1.5..1.5), 0.0, 0.0);
+            let pos = Vector3::new(plant.pos.x, 0.0, plant.pos.z) + offset;
+            seedlings.push(plant.offspring(env.next_plant_id, pos, variation, &mut env.rng));
+            env.next_plant_id += 1;
+        }
+        env.plants.extend(seedlings);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plants::tropisms::TropismKind;
 
     #[test]
     fn test_plant_engine_update() {
@@ -34,8 +71,37 @@ mod tests {
 
         let results = engine.update(1.0);
         assert_eq!(results.len(), 1); // One plant
-        assert_eq!(results[0].len(), 4); // Four tropisms applied
+        assert_eq!(results[0].len(), 5); // Five tropisms applied
+        let kinds: Vec<_> = results[0].iter().map(|r| r.kind).collect();
+        assert_eq!(kinds, TropismKind::ALL);
+        assert!(results[0].iter().all(|r| r.plant_id == 0 && r.step == 0));
         assert_ne!(engine.env.plants[0].pos, initial_pos); // Position changed
         assert_eq!(engine.env.time, 1.0); // Time incremented
+        assert_eq!(engine.env.step, 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_lifecycle_seeding_and_death() {
+        let mut engine = PlantEngine::new(SimulationEnv::new());
+        engine.env.plants[0].age = 100.0;
+        engine.env.plants[0].energy = 50.0;
+        engine.update(0.1);
+        assert_eq!(engine.env.plants.len(), 2); // Mature parent set a seed
+        assert_eq!(engine.env.plants[1].id, 1);
+
+        engine.env.plants[0].age = 1000.0; // Past lifespan
+        engine.update(0.1);
+        assert!(engine.env.plants.iter().all(|p| p.id != 0));
+    }
+
+    #[test]
+    fn test_same_seed_is_deterministic() {
+        let mut a = PlantEngine::new(SimulationEnv::with_seed(7));
+        let mut b = PlantEngine::new(SimulationEnv::with_seed(7));
+        for _ in 0..20 {
+            a.update(0.1);
+            b.update(0.1);
+        }
+        assert_eq!(a.env.plants[0].pos, b.env.plants[0].pos);
+    }
+}
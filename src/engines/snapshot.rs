@@ -0,0 +1,134 @@
+// src/engines/snapshot.rs
+use std::fmt;
+use nalgebra::{Matrix3, Vector3};
+use super::observables::Observable;
+
+/// Manifold state of one plant
+#[derive(Debug, Clone)]
+pub struct PlantSnapshot {
+    pub id: usize,
+    pub pos: Vector3<f32>,
+    pub rho_c: f32,
+    pub metric: Matrix3<f32>,
+    pub determinant: f32,
+    pub eigenvalues: Vector3<f32>, // Ascending
+    pub scalar_curvature: f64,     // Ricci scalar at the plant's (E, H, D)
+}
+
+/// Scalar values on a regular x–y grid over physical space
+#[derive(Debug, Clone, Default)]
+pub struct ScalarField {
+    pub origin: Vector3<f32>, // Center of cell (0, 0)
+    pub cell_size: f32,
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<f32>, // Row-major: index = j * width + i
+}
+
+impl ScalarField {
+    /// Evaluates `f` at every cell center
+    pub fn sample<F: Fn(&Vector3<f32>) -> f32>(origin: Vector3<f32>, cell_size: f32, width: usize, height: usize, f: F) -> Self {
+        let mut values = Vec::with_capacity(width * height);
+        for j in 0..height {
+            for i in 0..width {
+                values.push(f(&(origin + Vector3::new(i as f32, j as f32, 0.0) * cell_size)));
+            }
+        }
+        Self { origin, cell_size, width, height, values }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<f32> {
+        if i >= self.width {
+            return None;
+        }
+        self.values.get(j * self.width + i).copied()
+    }
+
+    pub fn cell_center(&self, i: usize, j: usize) -> Vector3<f32> {
+        self.origin + Vector3::new(i as f32, j as f32, 0.0) * self.cell_size
+    }
+
+    /// Smallest and largest finite value
+    pub fn range(&self) -> (f32, f32) {
+        self.values
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+    }
+}
+
+/// Scalar fields the engine samples over space each step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    ComplexDensity, // Aggregate ρ_c: each plant's ρ_c spread with a Gaussian kernel
+    Energy,         // E from light, water and soil nutrients
+    Distortion,     // D from obstacles
+    MetricVolume,   // √det G of the induced metric: local cost of moving through space
+}
+
+impl FieldKind {
+    pub const ALL: [FieldKind; 4] = [FieldKind::ComplexDensity, FieldKind::Energy, FieldKind::Distortion, FieldKind::MetricVolume];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldKind::ComplexDensity => "ρ_c",
+            FieldKind::Energy => "energy",
+            FieldKind::Distortion => "distortion",
+            FieldKind::MetricVolume => "√det G",
+        }
+    }
+}
+
+impl fmt::Display for FieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What `CSpaceEngine::update` produces each step
+#[derive(Debug, Clone, Default)]
+pub struct CSpaceSnapshot {
+    pub time: f32,
+    pub plants: Vec<PlantSnapshot>,
+    pub fields: Vec<(FieldKind, ScalarField)>, // One per `FieldKind::ALL`, on the same grid
+    pub observables: Vec<(Observable, f64)>,   // One per `Observable::ALL`
+}
+
+impl CSpaceSnapshot {
+    pub fn field(&self, kind: FieldKind) -> Option<&ScalarField> {
+        self.fields.iter().find(|(k, _)| *k == kind).map(|(_, field)| field)
+    }
+
+    pub fn observable(&self, kind: Observable) -> Option<f64> {
+        self.observables.iter().find(|(k, _)| *k == kind).map(|(_, v)| *v)
+    }
+}
+
+impl fmt::Display for CSpaceSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total: f32 = self.plants.iter().map(|p| p.rho_c).sum();
+        let (_, peak) = self.field(FieldKind::ComplexDensity).map_or((0.0, 0.0), ScalarField::range);
+        write!(
+            f,
+            "C-Space t={:.2}: plants={}, Σρ_c={:.2}, peak density={:.2}",
+            self.time,
+            self.plants.len(),
+            total,
+            if peak.is_finite() { peak } else { 0.0 }
+        )?;
+        if !self.observables.is_empty() {
+            write!(f, "\n ")?;
+            for (kind, value) in &self.observables {
+                write!(f, " {}={:.4}", kind, value)?;
+            }
+        }
+        for p in &self.plants {
+            write!(
+                f,
+                "\n  plant {}: ρ_c={:.2}, det g={:.4}, λ=({:.4}, {:.4}, {:.4}), R={:.3}",
+                p.id, p.rho_c, p.determinant, p.eigenvalues.x, p.eigenvalues.y, p.eigenvalues.z, p.scalar_curvature
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+// src/gui/bifurcation_window.rs
+use gtk4::prelude::*;
+use gtk4::{ApplicationWindow, Box as GtkBox, Button, ComboBoxText, DrawingArea, Label, Orientation};
+use crate::analysis::bifurcation::{bifurcation, BifurcationConfig, BifurcationPoint, BifurcationVariable, Regime};
+use crate::control::SimulationControl;
+use crate::simulation::batch::SweepAxis;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, TryRecvError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Parameters offered in the window and the ranges scanned for each
+const SCANS: [(&str, f32, f32); 3] = [("d_critical", 1.0, 20.0), ("alpha", 0.01, 0.3), ("beta", 0.01, 0.5)];
+
+fn regime_color(regime: Regime) -> (f64, f64, f64) {
+    match regime {
+        Regime::Equilibrium => (0.2, 0.4, 1.0),
+        Regime::Growth => (0.1, 0.7, 0.2),
+        Regime::Oscillation => (0.9, 0.6, 0.0),
+        Regime::Collapse => (0.8, 0.1, 0.1),
+        Regime::Extinct => (0.4, 0.4, 0.4),
+    }
+}
+
+pub fn build_bifurcation_window(app: gtk4::Application, control: Arc<SimulationControl>) -> ApplicationWindow {
+    let window = ApplicationWindow::new(&app);
+    window.set_title(Some("Bifurcation Diagram"));
+    window.set_default_size(600, 500);
+
+    let container = GtkBox::new(Orientation::Vertical, 10);
+    container.set_margin_start(10);
+    container.set_margin_end(10);
+    container.set_margin_top(10);
+    container.set_margin_bottom(10);
+
+    let controls = GtkBox::new(Orientation::Horizontal, 10);
+    let parameter_combo = ComboBoxText::new();
+    for (name, _, _) in SCANS {
+        parameter_combo.append_text(name);
+    }
+    parameter_combo.set_active(Some(0));
+    let variable_combo = ComboBoxText::new();
+    for name in BifurcationVariable::NAMES {
+        variable_combo.append_text(name);
+    }
+    variable_combo.set_active(Some(1)); // distortion
+    let run_btn = Button::with_label("Run Scan");
+    controls.append(&parameter_combo);
+    controls.append(&variable_combo);
+    controls.append(&run_btn);
+
+    let status = Label::new(Some("Scans the current scene; each value runs 500 + 200 steps."));
+    let drawing_area = DrawingArea::new();
+    drawing_area.set_size_request(560, 400);
+    drawing_area.set_vexpand(true);
+
+    container.append(&controls);
+    container.append(&status);
+    container.append(&drawing_area);
+
+    let points: Rc<RefCell<Vec<BifurcationPoint>>> = Rc::new(RefCell::new(Vec::new()));
+    let config: Rc<RefCell<Option<BifurcationConfig>>> = Rc::new(RefCell::new(None));
+
+    let points_draw = points.clone();
+    let config_draw = config.clone();
+    drawing_area.set_draw_func(move |_area, cr, width, height| {
+        let (width, height) = (width as f64, height as f64);
+        cr.set_source_rgb(1.0, 1.0, 1.0);
+        cr.paint().unwrap();
+
+        let points = points_draw.borrow();
+        let config = config_draw.borrow();
+        let Some(config) = config.as_ref() else { return };
+        if points.is_empty() {
+            return;
+        }
+
+        let margin = 50.0;
+        let values: Vec<f32> = points
+            .iter()
+            .flat_map(|p| p.samples.iter().map(|s| s.get(config.variable)))
+            .filter(|v| v.is_finite())
+            .collect();
+        let y_min = values.iter().copied().fold(f32::INFINITY, f32::min) as f64;
+        let y_max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max) as f64;
+        let y_span = if y_max > y_min { y_max - y_min } else { 1.0 };
+        let (x_min, x_max) = (config.parameter.min as f64, config.parameter.max as f64);
+        let x_span = if x_max > x_min { x_max - x_min } else { 1.0 };
+        let to_x = |v: f64| margin + (v - x_min) / x_span * (width - 2.0 * margin);
+        let to_y = |v: f64| height - margin - (v - y_min) / y_span * (height - 2.0 * margin);
+
+        // Axes and labels
+        cr.set_source_rgb(0.0, 0.0, 0.0);
+        cr.set_line_width(1.0);
+        cr.move_to(margin, margin);
+        cr.line_to(margin, height - margin);
+        cr.line_to(width - margin, height - margin);
+        cr.stroke().unwrap();
+        cr.set_font_size(11.0);
+        cr.move_to(width / 2.0 - 20.0, height - 15.0);
+        cr.show_text(&config.parameter.name).unwrap();
+        cr.move_to(5.0, margin - 10.0);
+        cr.show_text(config.variable.name()).unwrap();
+        for (text, x, y) in [
+            (format!("{:.3}", x_min), margin, height - margin + 15.0),
+            (format!("{:.3}", x_max), width - margin - 20.0, height - margin + 15.0),
+            (format!("{:.2}", y_min), 5.0, height - margin),
+            (format!("{:.2}", y_max), 5.0, margin + 10.0),
+        ] {
+            cr.move_to(x, y);
+            cr.show_text(&text).unwrap();
+        }
+
+        // Attractor samples, coloured by regime
+        for point in points.iter() {
+            let (r, g, b) = regime_color(point.regime);
+            cr.set_source_rgb(r, g, b);
+            let x = to_x(point.value as f64);
+            for sample in &point.samples {
+                let v = sample.get(config.variable) as f64;
+                if v.is_finite() {
+                    cr.rectangle(x - 1.0, to_y(v) - 1.0, 2.0, 2.0);
+                }
+            }
+            cr.fill().unwrap();
+        }
+
+        // Legend
+        for (i, regime) in Regime::ALL.iter().enumerate() {
+            let (r, g, b) = regime_color(*regime);
+            let y = margin + 15.0 * i as f64;
+            cr.set_source_rgb(r, g, b);
+            cr.rectangle(width - margin - 80.0, y - 8.0, 8.0, 8.0);
+            cr.fill().unwrap();
+            cr.set_source_rgb(0.0, 0.0, 0.0);
+            cr.move_to(width - margin - 68.0, y);
+            cr.show_text(regime.name()).unwrap();
+        }
+    });
+
+    run_btn.connect_clicked(move |button| {
+        let index = parameter_combo.active().unwrap_or(0) as usize;
+        let (name, min, max) = SCANS[index.min(SCANS.len() - 1)];
+        let mut scan = BifurcationConfig::new(SweepAxis { name: name.to_string(), min, max, points: 60 });
+        if let Some(variable) = variable_combo.active_text().and_then(|v| BifurcationVariable::parse(&v)) {
+            scan.variable = variable;
+        }
+        let template = control.env().lock().unwrap().clone();
+
+        // Run off the GUI thread and poll for the result
+        let (sender, receiver) = channel();
+        let worker_config = scan.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(bifurcation(&worker_config, &template));
+        });
+        button.set_sensitive(false);
+        status.set_text(&format!("Scanning {} over [{}, {}]...", name, min, max));
+
+        let button = button.clone();
+        let status = status.clone();
+        let points = points.clone();
+        let config = config.clone();
+        let drawing_area = drawing_area.clone();
+        glib::timeout_add_local(Duration::from_millis(100), move || match receiver.try_recv() {
+            Ok(result) => {
+                match result {
+                    Ok(result) => {
+                        status.set_text(&format!("{} values of {}", result.len(), scan.parameter.name));
+                        *points.borrow_mut() = result;
+                        *config.borrow_mut() = Some(scan.clone());
+                        drawing_area.queue_draw();
+                    }
+                    Err(e) => status.set_text(&format!("Scan failed: {}", e)),
+                }
+                button.set_sensitive(true);
+                glib::ControlFlow::Break
+            }
+            Err(TryRecvError::Empty) => glib::ControlFlow::Continue,
+            Err(TryRecvError::Disconnected) => {
+                status.set_text("Scan failed");
+                button.set_sensitive(true);
+                glib::ControlFlow::Break
+            }
+        });
+    });
+
+    window.set_child(Some(&container));
+    window
+}
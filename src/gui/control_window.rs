@@ -1,8 +1,29 @@
 use gtk4::prelude::*;
-use gtk4::{Application, ApplicationWindow, Button, Box as GtkBox, Orientation};
+use gtk4::{Application, ApplicationWindow, Button, Box as GtkBox, CheckButton, ComboBoxText, Entry, Label, Orientation, Scale, SpinButton, ToggleButton};
+use nalgebra::Vector3;
+use rand::Rng;
+use std::cell::Cell;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use crate::control::SimulationControl;
 use crate::plants::tropisms::Environment;
+use crate::simulation::checkpoint::Checkpoint;
+use crate::simulation::export::{ExportFormat, Exporter};
+use crate::simulation::geometry::{export_geometry, GeometryOptions};
+use crate::simulation::recording::Recorder;
+use crate::simulation::scenario::{Scenario, EXAMPLES};
+use crate::simulation::simulation_runner::{ObserverId, SimulationObserver, SimulationRunner, Speed};
+
+/// Entries of the speed picker
+const SPEEDS: [(&str, Speed); 6] = [
+    ("0.25x", Speed::RealTime(0.25)),
+    ("0.5x", Speed::RealTime(0.5)),
+    ("1x", Speed::RealTime(1.0)),
+    ("2x", Speed::RealTime(2.0)),
+    ("10x", Speed::RealTime(10.0)),
+    ("Max", Speed::Unlimited),
+];
 
 pub fn build_control_window(
     app: Application,
@@ -24,8 +45,129 @@ pub fn build_control_window(
     vbox.append(&start_btn);
     vbox.append(&stop_btn);
 
+    // Pause, single-step and speed
+    let pause_btn = Button::with_label("Pause");
+    let resume_btn = Button::with_label("Resume");
+    let step_btn = Button::with_label("Step");
+    let step_count = SpinButton::with_range(1.0, 10000.0, 1.0);
+    let run_row = GtkBox::new(Orientation::Horizontal, 5);
+    run_row.append(&pause_btn);
+    run_row.append(&resume_btn);
+    run_row.append(&step_btn);
+    run_row.append(&step_count);
+    vbox.append(&run_row);
+
+    let speed_combo = ComboBoxText::new();
+    for (name, _) in SPEEDS {
+        speed_combo.append_text(name);
+    }
+    speed_combo.set_active(Some(2));
+    let dt_spin = SpinButton::with_range(0.001, 10.0, 0.01);
+    dt_spin.set_digits(3);
+    dt_spin.set_value(SimulationRunner::DT as f64);
+    let speed_row = GtkBox::new(Orientation::Horizontal, 5);
+    speed_row.append(&Label::new(Some("Speed")));
+    speed_row.append(&speed_combo);
+    speed_row.append(&Label::new(Some("dt")));
+    speed_row.append(&dt_spin);
+    vbox.append(&speed_row);
+
+    // Scenarios: a bundled example or a TOML file path
+    let scenario_combo = ComboBoxText::with_entry();
+    for (name, _) in EXAMPLES {
+        scenario_combo.append_text(name);
+    }
+    scenario_combo.set_active(Some(0));
+    let scenario_btn = Button::with_label("Load Scenario");
+    let scenario_row = GtkBox::new(Orientation::Horizontal, 5);
+    scenario_row.append(&Label::new(Some("Scenario")));
+    scenario_row.append(&scenario_combo);
+    scenario_row.append(&scenario_btn);
+    vbox.append(&scenario_row);
+
+    // Checkpoints
+    let checkpoint_path = Entry::new();
+    checkpoint_path.set_text("simulation.ckpt");
+    checkpoint_path.set_hexpand(true);
+    let save_btn = Button::with_label("Save");
+    let load_btn = Button::with_label("Load");
+    let checkpoint_row = GtkBox::new(Orientation::Horizontal, 5);
+    checkpoint_row.append(&Label::new(Some("Checkpoint")));
+    checkpoint_row.append(&checkpoint_path);
+    checkpoint_row.append(&save_btn);
+    checkpoint_row.append(&load_btn);
+    vbox.append(&checkpoint_row);
+
+    // Recording every step to a file for replay
+    let recording_path = Entry::new();
+    recording_path.set_text("simulation.rec");
+    recording_path.set_hexpand(true);
+    let record_btn = ToggleButton::with_label("Record");
+    let recording_row = GtkBox::new(Orientation::Horizontal, 5);
+    recording_row.append(&Label::new(Some("Recording")));
+    recording_row.append(&recording_path);
+    recording_row.append(&record_btn);
+    vbox.append(&recording_row);
+
+    // Exporting per-plant rows of every step to CSV or NPY
+    let export_path = Entry::new();
+    export_path.set_text("trajectories.csv");
+    export_path.set_hexpand(true);
+    let export_btn = ToggleButton::with_label("Export");
+    let export_row = GtkBox::new(Orientation::Horizontal, 5);
+    export_row.append(&Label::new(Some("Export")));
+    export_row.append(&export_path);
+    export_row.append(&export_btn);
+    vbox.append(&export_row);
+
+    // Writing the current plants as a mesh for ParaView or Blender
+    let geometry_path = Entry::new();
+    geometry_path.set_text("plants.obj");
+    geometry_path.set_hexpand(true);
+    let geometry_attributes = CheckButton::with_label("Attributes");
+    let geometry_btn = Button::with_label("Save");
+    let geometry_row = GtkBox::new(Orientation::Horizontal, 5);
+    geometry_row.append(&Label::new(Some("Geometry")));
+    geometry_row.append(&geometry_path);
+    geometry_row.append(&geometry_attributes);
+    geometry_row.append(&geometry_btn);
+    vbox.append(&geometry_row);
+
+    // Timeline, when replaying a recording
+    let timeline = control.status().timeline;
+    let rewind_btn = Button::with_label("Rewind");
+    let back_btn = Button::with_label("Back");
+    let (first, last) = timeline.unwrap_or((0, 1));
+    let timeline_scale = Scale::with_range(Orientation::Horizontal, first as f64, last.max(first + 1) as f64, 1.0);
+    timeline_scale.set_hexpand(true);
+    let timeline_row = GtkBox::new(Orientation::Horizontal, 5);
+    timeline_row.append(&rewind_btn);
+    timeline_row.append(&back_btn);
+    timeline_row.append(&timeline_scale);
+    timeline_row.set_visible(timeline.is_some());
+    vbox.append(&timeline_row);
+
+    let status_label = Label::new(Some(&control.status().to_string()));
+    status_label.set_wrap(true);
+    vbox.append(&status_label);
+
+    // Species picker
+    let species = control.species();
+    let species_combo = ComboBoxText::new();
+    for s in &species {
+        species_combo.append_text(&s.name);
+    }
+    species_combo.set_active(Some(0));
+    let species_info = Label::new(species.first().map(|s| s.description.as_str()));
+    let plant_btn = Button::with_label("Plant Selected Species");
+    vbox.append(&Label::new(Some("Species")));
+    vbox.append(&species_combo);
+    vbox.append(&species_info);
+    vbox.append(&plant_btn);
+
     let control_start = control.clone();
     let control_stop = control.clone();
+    let control_plant = control.clone();
 
     start_btn.connect_clicked(move |_| {
         control_start.start();
@@ -35,5 +177,158 @@ pub fn build_control_window(
         control_stop.stop();
     });
 
+    let control_pause = control.clone();
+    pause_btn.connect_clicked(move |_| control_pause.pause());
+    let control_resume = control.clone();
+    resume_btn.connect_clicked(move |_| control_resume.resume());
+    let control_step = control.clone();
+    step_btn.connect_clicked(move |_| control_step.step(step_count.value() as u64));
+    let control_speed = control.clone();
+    speed_combo.connect_changed(move |combo| {
+        if let Some((_, speed)) = combo.active().and_then(|idx| SPEEDS.get(idx as usize)) {
+            control_speed.set_speed(*speed);
+        }
+    });
+    let control_dt = control.clone();
+    dt_spin.connect_value_changed(move |spin| control_dt.set_dt(spin.value() as f32));
+
+    let control_scenario = control.clone();
+    scenario_btn.connect_clicked(move |_| {
+        let Some(name) = scenario_combo.active_text() else { return };
+        match Scenario::open(&name).and_then(|scenario| scenario.build()) {
+            Ok(env) => control_scenario.restore(Checkpoint { env, dt: control_scenario.status().dt }),
+            Err(e) => eprintln!("Failed to load scenario {}: {}", name, e),
+        }
+    });
+
+    let control_save = control.clone();
+    let save_path = checkpoint_path.clone();
+    save_btn.connect_clicked(move |_| {
+        let path = save_path.text().to_string();
+        match control_save.checkpoint().map(|c| c.save(Path::new(&path))) {
+            Some(Ok(())) => println!("Checkpoint saved to {}", path),
+            Some(Err(e)) => eprintln!("Failed to save {}: {}", path, e),
+            None => eprintln!("Simulation did not answer the checkpoint request"),
+        }
+    });
+    let control_load = control.clone();
+    load_btn.connect_clicked(move |_| {
+        let path = checkpoint_path.text().to_string();
+        match Checkpoint::load(Path::new(&path)) {
+            Ok(checkpoint) => control_load.restore(checkpoint),
+            Err(e) => eprintln!("Failed to load {}: {}", path, e),
+        }
+    });
+
+    observer_toggle(&record_btn, control.clone(), move || {
+        let path = recording_path.text().to_string();
+        match Recorder::create(Path::new(&path)) {
+            Ok(recorder) => {
+                println!("Recording to {}", path);
+                Some(Box::new(recorder))
+            }
+            Err(e) => {
+                eprintln!("Failed to record to {}: {}", path, e);
+                None
+            }
+        }
+    });
+
+    observer_toggle(&export_btn, control.clone(), move || {
+        let path = export_path.text().to_string();
+        let Some(format) = ExportFormat::from_path(Path::new(&path)) else {
+            eprintln!("Cannot export to {}: use a .csv or .npy file", path);
+            return None;
+        };
+        match Exporter::create(Path::new(&path), format) {
+            Ok(exporter) => {
+                println!("Exporting to {}", path);
+                Some(Box::new(exporter))
+            }
+            Err(e) => {
+                eprintln!("Failed to export to {}: {}", path, e);
+                None
+            }
+        }
+    });
+
+    let control_geometry = control.clone();
+    geometry_btn.connect_clicked(move |_| {
+        let path = geometry_path.text().to_string();
+        let options = GeometryOptions { attributes: geometry_attributes.is_active(), ..GeometryOptions::default() };
+        let plants = control_geometry.plants().lock().unwrap().clone();
+        let environment = control_geometry.environment().lock().unwrap().clone();
+        match export_geometry(Path::new(&path), &plants, &environment, &options) {
+            Ok(()) => println!("Plant geometry written to {}", path),
+            Err(e) => eprintln!("Failed to write {}: {}", path, e),
+        }
+    });
+
+    let control_rewind = control.clone();
+    rewind_btn.connect_clicked(move |_| control_rewind.seek(first));
+    let control_back = control.clone();
+    back_btn.connect_clicked(move |_| control_back.seek(control_back.status().step.saturating_sub(1)));
+    let control_seek = control.clone();
+    timeline_scale.connect_change_value(move |_, _, value| {
+        control_seek.seek(value.round().max(0.0) as u64);
+        glib::Propagation::Proceed
+    });
+
+    // Show the runner's state and achieved rate until the window is gone
+    let control_status = control.clone();
+    let status_weak = status_label.downgrade();
+    glib::timeout_add_local(Duration::from_millis(250), move || match status_weak.upgrade() {
+        Some(label) => {
+            let status = control_status.status();
+            label.set_text(&status.to_string());
+            if status.timeline.is_some() {
+                timeline_scale.set_value(status.step as f64);
+            }
+            glib::ControlFlow::Continue
+        }
+        None => glib::ControlFlow::Break,
+    });
+
+    species_combo.connect_changed(move |combo| {
+        let idx = combo.active().unwrap_or(0) as usize;
+        if let Some(s) = species.get(idx) {
+            species_info.set_text(&s.description);
+        }
+    });
+
+    let species_combo_plant = species_combo.clone();
+    plant_btn.connect_clicked(move |_| {
+        if let Some(name) = species_combo_plant.active_text() {
+            let pos = Vector3::new(rand::thread_rng().gen_range(-5.0..5.0), 0.0, 0.0);
+            match control_plant.spawn_plant(&name, pos) {
+                Some(id) => println!("Planted {} (id {}) at {:?}", name, id, pos),
+                None => eprintln!("Unknown species: {}", name),
+            }
+        }
+    });
+
     window
-}
\ No newline at end of file
+}
+
+/// Runs an observer while `button` is active: `start` creates it, or reports why it cannot and
+/// returns None, which releases the button. Releasing detaches the observer, and dropping it on
+/// the runner flushes or finalizes its file right away.
+fn observer_toggle(
+    button: &ToggleButton,
+    control: Arc<SimulationControl>,
+    start: impl Fn() -> Option<Box<dyn SimulationObserver>> + 'static,
+) {
+    let attached: Cell<Option<ObserverId>> = Cell::new(None);
+    button.connect_toggled(move |button| {
+        if !button.is_active() {
+            if let Some(id) = attached.take() {
+                control.detach(id);
+            }
+            return;
+        }
+        match start() {
+            Some(observer) => attached.set(Some(control.attach_observer(observer))),
+            None => button.set_active(false),
+        }
+    });
+}
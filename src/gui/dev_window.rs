@@ -6,12 +6,12 @@ use vte4::Terminal as VteTerminal;
 use vte4::{TerminalExt, TerminalExtManual}; // For spawn_async
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc::Receiver;
-use crate::plants::tropisms::TropismResult;
+use crate::simulation::simulation_runner::SimulationEvent;
 
 pub fn build_dev_window(
     app: gtk4::Application,
     logs: Arc<Mutex<Vec<String>>>,
-    log_receiver: Arc<Mutex<Receiver<Vec<Vec<TropismResult>>>>>,
+    log_receiver: Receiver<SimulationEvent>,
 ) -> ApplicationWindow {
     let window = ApplicationWindow::new(&app);
     window.set_title(Some("Development Logs & CLI"));
@@ -62,15 +62,23 @@ pub fn build_dev_window(
     // Handle logs
     let terminal_clone = terminal.clone();
     let log_buffer_clone = log_buffer.clone();
-    let log_receiver_clone = log_receiver.clone();
     glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-        let receiver = log_receiver_clone.lock().unwrap();
         let mut new_logs = Vec::new();
-        while let Ok(results_vec) = receiver.try_recv() {
-            for plant_results in &results_vec {
-                for result in plant_results {
-                    new_logs.push(result.log.clone());
+        while let Ok(event) = log_receiver.try_recv() {
+            match event {
+                SimulationEvent::Step(results_vec) => {
+                    for plant_results in &results_vec {
+                        for result in plant_results {
+                            new_logs.push(result.to_string());
+                        }
+                    }
                 }
+                SimulationEvent::Snapshot(snapshot) => new_logs.push(snapshot.to_string()),
+                SimulationEvent::Status(status) => new_logs.push(status.to_string()),
+                SimulationEvent::Mutation(mutation, result) => match result {
+                    Ok(_) => new_logs.push(format!("Applied: {}", mutation)),
+                    Err(e) => new_logs.push(format!("Rejected: {} ({})", mutation, e)),
+                },
             }
         }
         if !new_logs.is_empty() {
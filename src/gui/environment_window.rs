@@ -1,12 +1,15 @@
 use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Box as GtkBox, Label, Orientation};
+use gtk4::{ApplicationWindow, Box as GtkBox, ComboBoxText, Label, Orientation};
 use nalgebra::Matrix3;
+use crate::control::SimulationControl;
+use crate::plants::metric::{MetricFormulation, MetricModel};
 use crate::plants::tropisms::Environment;
 use std::sync::{Arc, Mutex};
 
 pub fn build_environment_window(
     app: gtk4::Application,
     environment: Arc<Mutex<Environment>>,
+    control: Arc<SimulationControl>,
 ) -> ApplicationWindow {
     let window = ApplicationWindow::new(&app);
     window.set_title(Some("Environment Overview"));
@@ -26,6 +29,11 @@ pub fn build_environment_window(
     container.append(&Label::new(Some(&format!("Light Intensity: {:.2}", env.light_intensity))));
     container.append(&Label::new(Some(&format!("Water Level: {:.2}", env.water_level))));
     container.append(&Label::new(Some(&format!("Obstacles: {}", env.obstacles.len()))));
+    let soil = env.nutrients.totals();
+    container.append(&Label::new(Some(&format!(
+        "Soil Nutrients: N={:.2} P={:.2} K={:.2}",
+        soil.nitrogen, soil.phosphorus, soil.potassium
+    ))));
 
     let tensor = env.metric_tensor;
     container.append(&Label::new(Some("Metric Tensor")));
@@ -43,6 +51,23 @@ pub fn build_environment_window(
     ))));
     container.append(&Label::new(Some(&format!("Critical Distortion: {:.2}", env.d_critical))));
 
+    // Metric formulation, applied from the next step on
+    let metric_combo = ComboBoxText::new();
+    for formulation in MetricFormulation::ALL {
+        metric_combo.append_text(formulation.name());
+    }
+    metric_combo.set_active(MetricFormulation::ALL.iter().position(|f| *f == env.metric.formulation).map(|i| i as u32));
+    container.append(&Label::new(Some(&format!("Metric Formulation (coupling {:.2})", env.metric.coupling))));
+    container.append(&metric_combo);
+    drop(env);
+
+    metric_combo.connect_changed(move |combo| {
+        if let Some(formulation) = combo.active_text().and_then(|name| MetricFormulation::parse(&name)) {
+            let metric = environment.lock().unwrap().metric;
+            control.set_metric(MetricModel { formulation, ..metric });
+        }
+    });
+
     window.set_child(Some(&container));
     window
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+// src/gui/heatmap.rs
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, CheckButton, DrawingArea, Grid, Label, Orientation, Scale, SpinButton};
+use crate::render::scene::OverlaySettings;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// One row per field (show, auto range, min, max) and an opacity slider. Every change redraws `area`.
+pub fn build_overlay_controls(settings: Rc<RefCell<OverlaySettings>>, area: &DrawingArea) -> GtkBox {
+    let container = GtkBox::new(Orientation::Vertical, 5);
+    let grid = Grid::new();
+    grid.set_column_spacing(10);
+    grid.set_row_spacing(5);
+    for (column, title) in ["Field", "Auto", "Min", "Max"].iter().enumerate() {
+        grid.attach(&Label::new(Some(title)), column as i32, 0, 1, 1);
+    }
+
+    let layers = settings.borrow().layers.clone();
+    for (index, layer) in layers.iter().enumerate() {
+        let row = index as i32 + 1;
+        let show = CheckButton::with_label(layer.kind.name());
+        show.set_active(layer.enabled);
+        let auto = CheckButton::new();
+        auto.set_active(layer.auto_range);
+        let min = SpinButton::with_range(-1e6, 1e6, 0.1);
+        let max = SpinButton::with_range(-1e6, 1e6, 0.1);
+        min.set_digits(3);
+        max.set_digits(3);
+        min.set_value(layer.min as f64);
+        max.set_value(layer.max as f64);
+        min.set_sensitive(!layer.auto_range);
+        max.set_sensitive(!layer.auto_range);
+        grid.attach(&show, 0, row, 1, 1);
+        grid.attach(&auto, 1, row, 1, 1);
+        grid.attach(&min, 2, row, 1, 1);
+        grid.attach(&max, 3, row, 1, 1);
+
+        let (settings_show, area_show) = (settings.clone(), area.clone());
+        show.connect_toggled(move |button| {
+            settings_show.borrow_mut().layers[index].enabled = button.is_active();
+            area_show.queue_draw();
+        });
+
+        let (settings_auto, area_auto) = (settings.clone(), area.clone());
+        let (min_auto, max_auto) = (min.clone(), max.clone());
+        auto.connect_toggled(move |button| {
+            let active = button.is_active();
+            let (lo, hi) = {
+                let mut settings = settings_auto.borrow_mut();
+                let layer = &mut settings.layers[index];
+                layer.auto_range = active;
+                (layer.min, layer.max)
+            };
+            // Manual mode starts from the last automatic range
+            min_auto.set_value(lo as f64);
+            max_auto.set_value(hi as f64);
+            min_auto.set_sensitive(!active);
+            max_auto.set_sensitive(!active);
+            area_auto.queue_draw();
+        });
+
+        let (settings_min, area_min) = (settings.clone(), area.clone());
+        min.connect_value_changed(move |spin| {
+            let mut settings = settings_min.borrow_mut();
+            if !settings.layers[index].auto_range {
+                settings.layers[index].min = spin.value() as f32;
+                area_min.queue_draw();
+            }
+        });
+
+        let (settings_max, area_max) = (settings.clone(), area.clone());
+        max.connect_value_changed(move |spin| {
+            let mut settings = settings_max.borrow_mut();
+            if !settings.layers[index].auto_range {
+                settings.layers[index].max = spin.value() as f32;
+                area_max.queue_draw();
+            }
+        });
+    }
+
+    let opacity_row = GtkBox::new(Orientation::Horizontal, 10);
+    let opacity = Scale::with_range(Orientation::Horizontal, 0.0, 1.0, 0.05);
+    opacity.set_value(settings.borrow().opacity);
+    opacity.set_hexpand(true);
+    opacity_row.append(&Label::new(Some("Opacity")));
+    opacity_row.append(&opacity);
+    let area_opacity = area.clone();
+    opacity.connect_value_changed(move |scale| {
+        settings.borrow_mut().opacity = scale.value();
+        area_opacity.queue_draw();
+    });
+
+    container.append(&grid);
+    container.append(&opacity_row);
+    container
+}
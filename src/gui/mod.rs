@@ -1,10 +1,13 @@
+pub mod bifurcation_window;
 pub mod control_window;
 pub mod dev_window;
 pub mod environment_window;
+pub mod heatmap;
 pub mod plant_diagnostics_window;
 pub mod simulation_window;
 pub mod startup_window;
 
+pub use bifurcation_window::build_bifurcation_window;
 pub use control_window::build_control_window;
 pub use dev_window::build_dev_window;
 pub use environment_window::build_environment_window;
@@ -41,6 +41,8 @@ pub fn build_plant_diagnostics_window(
         
         if idx > 0 && idx - 1 < plants.len() {
             if let Some(plant) = plants.get(idx - 1) {
+                details.append(&Label::new(Some(&format!("Species: {} (id {})", plant.species, plant.id))));
+                details.append(&Label::new(Some(&format!("Age: {:.1}", plant.age))));
                 details.append(&Label::new(Some(&format!("Position: {:?}", plant.pos))));
                 // ... other append calls ...
             }
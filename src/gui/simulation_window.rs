@@ -1,70 +1,43 @@
 // src/gui/simulation_window.rs
 use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, DrawingArea};
+use gtk4::{ApplicationWindow, Box as GtkBox, DrawingArea, Orientation};
 use crate::plants::tropisms::Plant;
-use crate::engines::plant_engine::PlantEngine;
+use crate::engines::snapshot::CSpaceSnapshot;
+use crate::gui::heatmap::build_overlay_controls;
+use crate::render::scene::{draw_scene, OverlaySettings, Scene};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use std::rc::Rc; // Add this import
+use std::time::Duration;
 
 pub fn build_simulation_window(
     app: gtk4::Application,
     plants: Arc<Mutex<Vec<Plant>>>,
-    engine: &mut PlantEngine,
     logs: Arc<Mutex<Vec<String>>>,
     environment: Arc<Mutex<crate::plants::tropisms::Environment>>,
+    snapshot: Arc<Mutex<Option<CSpaceSnapshot>>>,
 ) -> ApplicationWindow {
     let window = ApplicationWindow::new(&app);
     window.set_title(Some("Simulation View"));
-    window.set_default_size(400, 400);
+    window.set_default_size(400, 600);
 
     let drawing_area = DrawingArea::new();
     drawing_area.set_size_request(400, 400);
+    drawing_area.set_vexpand(true);
 
-    // Set up the drawing function
+    let overlay = Rc::new(RefCell::new(OverlaySettings::default()));
+    let overlay_draw = overlay.clone();
+
+    // Set up the drawing function; the same renderer draws offscreen frames
     drawing_area.set_draw_func(move |_area, cr, width, height| {
         let plants = plants.lock().unwrap();
         let env = environment.lock().unwrap();
-        let scale = 20.0;
-        let center_x = width as f64 / 2.0;
-        let center_y = height as f64 / 2.0;
-
-        // Draw plants
-        for plant in plants.iter() {
-            let pos_x = center_x + plant.pos.x as f64 * scale;
-            let pos_y = center_y - plant.pos.y as f64 * scale;
-
-            let stem_x = pos_x + plant.stem_dir.x as f64 * scale;
-            let stem_y = pos_y - plant.stem_dir.y as f64 * scale;
-            let root_x = pos_x + plant.root_dir.x as f64 * scale;
-            let root_y = pos_y - plant.root_dir.y as f64 * scale;
-
-            cr.set_source_rgb(0.0, 1.0, 0.0); // Green for stem
-            cr.move_to(pos_x, pos_y);
-            cr.line_to(stem_x, stem_y);
-            cr.stroke().unwrap();
-
-            cr.set_source_rgb(0.65, 0.16, 0.16); // Brown for roots
-            cr.move_to(pos_x, pos_y);
-            cr.line_to(root_x, root_y);
-            cr.stroke().unwrap();
-
-            cr.set_source_rgb(1.0, 0.0, 0.0); // Red dot for plant base
-            cr.arc(pos_x, pos_y, 3.0, 0.0, 2.0 * std::f64::consts::PI);
-            cr.fill().unwrap();
+        let snapshot = snapshot.lock().unwrap();
+        let scene = Scene { plants: &plants, environment: &env, snapshot: snapshot.as_ref() };
+        // A failed frame is skipped; the next redraw tries again
+        if let Err(e) = draw_scene(cr, width as f64, height as f64, &scene, &mut overlay_draw.borrow_mut()) {
+            eprintln!("Failed to draw the scene: {}", e);
         }
-
-        // Optionally draw environment elements (e.g., light, water)
-        let light_x = center_x + env.light_pos.x as f64 * scale;
-        let light_y = center_y - env.light_pos.y as f64 * scale;
-        cr.set_source_rgb(1.0, 1.0, 0.0); // Yellow for light
-        cr.arc(light_x, light_y, 5.0, 0.0, 2.0 * std::f64::consts::PI);
-        cr.fill().unwrap();
-
-        let water_x = center_x + env.water_pos.x as f64 * scale;
-        let water_y = center_y - env.water_pos.y as f64 * scale;
-        cr.set_source_rgb(0.0, 0.0, 1.0); // Blue for water
-        cr.arc(water_x, water_y, 5.0, 0.0, 2.0 * std::f64::consts::PI);
-        cr.fill().unwrap();
     });
 
     // Create a clone of drawing_area before moving it into the closure
@@ -74,6 +47,24 @@ pub fn build_simulation_window(
         drawing_area_clone.queue_draw();
     });
 
-    window.set_child(Some(&drawing_area));
+    // Redraw at the runner's rate until the window is gone
+    let drawing_area_weak = drawing_area.downgrade();
+    glib::timeout_add_local(Duration::from_millis(100), move || match drawing_area_weak.upgrade() {
+        Some(area) => {
+            area.queue_draw();
+            glib::ControlFlow::Continue
+        }
+        None => glib::ControlFlow::Break,
+    });
+
+    let container = GtkBox::new(Orientation::Vertical, 10);
+    container.set_margin_start(10);
+    container.set_margin_end(10);
+    container.set_margin_top(10);
+    container.set_margin_bottom(10);
+    container.append(&drawing_area);
+    container.append(&build_overlay_controls(overlay, &drawing_area));
+
+    window.set_child(Some(&container));
     window
 }
\ No newline at end of file
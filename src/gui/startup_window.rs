@@ -3,19 +3,15 @@ use gtk4::Application;
 use gtk4::{ApplicationWindow, Button, Box as GtkBox, Orientation};
 use crate::control::SimulationControl;
 use crate::gui::{
-    control_window, dev_window, environment_window, plant_diagnostics_window, simulation_window,
+    bifurcation_window, control_window, dev_window, environment_window, plant_diagnostics_window, simulation_window,
 };
-use std::sync::{Arc, Mutex};
-use std::sync::mpsc::{Sender, Receiver};
-use crate::plants::tropisms::TropismResult;
-use crate::simulation::simulation_runner::ControlCommand;
+use std::sync::Arc;
 
-pub fn launch_with_runner(command_sender: Sender<ControlCommand>, log_receiver: Receiver<Vec<Vec<TropismResult>>>) {
+/// Runs the GTK application on top of a runner that `control` is attached to
+pub fn launch_with_runner(control: Arc<SimulationControl>) {
     let app = Application::new(Some("com.example.simulator"), Default::default());
-    let log_receiver = Arc::new(Mutex::new(log_receiver)); // Wrap in Arc<Mutex>
 
     app.connect_activate(move |app| {
-        let control = Arc::new(SimulationControl::new(crate::simulation::simulation_env::SimulationEnv::new()));
         let app_clone = app.clone();
 
         let window = ApplicationWindow::builder()
@@ -33,26 +29,27 @@ pub fn launch_with_runner(command_sender: Sender<ControlCommand>, log_receiver:
         let env_btn = Button::with_label("Open Environment Window");
         let diag_btn = Button::with_label("Open Diagnostics Window");
         let sim_btn = Button::with_label("Open Simulation Window");
+        let bifurcation_btn = Button::with_label("Open Bifurcation Window");
 
         vbox.append(&control_btn);
         vbox.append(&dev_btn);
         vbox.append(&env_btn);
         vbox.append(&diag_btn);
         vbox.append(&sim_btn);
+        vbox.append(&bifurcation_btn);
 
         let control_control = control.clone();
         let control_dev = control.clone();
         let control_env = control.clone();
         let control_diag = control.clone();
         let control_sim = control.clone();
+        let control_bifurcation = control.clone();
         let app_clone_control = app_clone.clone();
         let app_clone_dev = app_clone.clone();
         let app_clone_env = app_clone.clone();
         let app_clone_diag = app_clone.clone();
         let app_clone_sim = app_clone.clone();
-        let command_sender_control = command_sender.clone();
-        let command_sender_dev = command_sender.clone();
-        let log_receiver_dev = log_receiver.clone(); // Clone the Arc
+        let app_clone_bifurcation = app_clone.clone();
 
         control_btn.connect_clicked(move |_| {
             let control_win = control_window::build_control_window(
@@ -61,14 +58,13 @@ pub fn launch_with_runner(command_sender: Sender<ControlCommand>, log_receiver:
                 control_control.clone(),
             );
             control_win.present();
-            command_sender_control.send(ControlCommand::Start).unwrap();
         });
 
         dev_btn.connect_clicked(move |_| {
             let dev_win = dev_window::build_dev_window(
                 app_clone_dev.clone(),
                 control_dev.logs(),
-                log_receiver_dev.clone(), // Pass only log_receiver
+                control_dev.subscribe(),
             );
             dev_win.present();
         });
@@ -77,6 +73,7 @@ pub fn launch_with_runner(command_sender: Sender<ControlCommand>, log_receiver:
             let env_win = environment_window::build_environment_window(
                 app_clone_env.clone(),
                 control_env.environment(),
+                control_env.clone(),
             );
             env_win.present();
         });
@@ -90,20 +87,27 @@ pub fn launch_with_runner(command_sender: Sender<ControlCommand>, log_receiver:
         });
 
         sim_btn.connect_clicked(move |_| {
-            let engine_lock = control_sim.engine();
-            let mut engine = engine_lock.lock().unwrap();
             let sim_win = simulation_window::build_simulation_window(
                 app_clone_sim.clone(),
                 control_sim.plants(),
-                &mut engine,
                 control_sim.logs(),
                 control_sim.environment(),
+                control_sim.snapshot(),
             );
             sim_win.present();
         });
 
+        bifurcation_btn.connect_clicked(move |_| {
+            let bifurcation_win = bifurcation_window::build_bifurcation_window(
+                app_clone_bifurcation.clone(),
+                control_bifurcation.clone(),
+            );
+            bifurcation_win.present();
+        });
+
         window.present();
     });
 
-    app.run();
+    // Our own options (such as --replay) are not GTK's, so GTK gets none
+    app.run_with_args::<&str>(&[]);
 }
\ No newline at end of file
@@ -1,9 +1,13 @@
 // src/lib.rs
+pub mod analysis;
 pub mod control;
 pub mod engines;
+#[cfg(feature = "gui")]
 pub mod gui;
 pub mod plants;
+pub mod render;
 pub mod simulation;
 
+#[cfg(feature = "gui")]
 pub use gui::startup_window::launch_with_runner;
 pub use simulation::simulation_runner::SimulationRunner;
\ No newline at end of file
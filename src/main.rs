@@ -1,22 +1,57 @@
+mod analysis;
 mod control;
 mod engines;
 mod gui;
 mod plants;
 mod simulation;
 
+use crate::control::SimulationControl;
 use crate::gui::startup_window::launch_with_runner;
-use std::sync::mpsc::channel;
+use crate::simulation::recording::Recording;
+use crate::simulation::replay::Replayer;
+use crate::simulation::simulation_env::SimulationEnv;
+use crate::simulation::simulation_runner::SimulationRunner;
+use std::path::Path;
+use std::sync::Arc;
 
 fn main() {
     println!("Starting main function...");
 
-    // Create channels for communication
-    let (command_sender, command_receiver) = channel();
-    let (_log_sender, log_receiver) = channel();
+    // The runner owns the simulation; the GUI talks to it through a SimulationControl handle.
+    // `--replay <file>` puts a Replayer of a recording in its place.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (control, runner_thread) = match args.as_slice() {
+        [flag, path] if flag == "--replay" => {
+            let recording = match Recording::load(Path::new(path)) {
+                Ok(recording) => recording,
+                Err(e) => {
+                    eprintln!("Error: cannot replay {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            };
+            let (mut replayer, command_sender) = Replayer::new(recording);
+            let control = Arc::new(SimulationControl::attach(&mut replayer, command_sender));
+            println!("Replaying {}.", path);
+            (control, replayer.spawn())
+        }
+        [] => {
+            let (mut runner, command_sender) = SimulationRunner::new(SimulationEnv::new());
+            let control = Arc::new(SimulationControl::attach(&mut runner, command_sender));
+            println!("SimulationRunner started.");
+            (control, runner.spawn())
+        }
+        _ => {
+            eprintln!("Usage: cs_simulator [--replay <recording>]");
+            std::process::exit(1);
+        }
+    };
 
     println!("Launching GTK application...");
-    // Launch GTK app with channels; SimulationRunner will be started later via dev_window
-    launch_with_runner(command_sender, log_receiver);
+    launch_with_runner(control.clone());
 
     println!("GTK application exited.");
-}
\ No newline at end of file
+    control.shutdown();
+    if runner_thread.join().is_err() {
+        eprintln!("SimulationRunner panicked.");
+    }
+}
@@ -0,0 +1,170 @@
+// src/plants/metric.rs
+use std::fmt;
+use nalgebra::{Matrix3, RealField, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// Named ways of building the metric g on (E, H, D)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MetricFormulation {
+    /// diag(1/E², 1/E, 1/(D+ε)): the original model, blind to coherence
+    #[default]
+    Diagonal,
+    /// diag(1/E², (1+H²)/E, 1/(D+ε)): coherent structure makes H moves costlier
+    Coherent,
+    /// `Coherent` scales with off-diagonal couplings: E and H move together, D opposes both
+    Coupled,
+}
+
+impl MetricFormulation {
+    pub const ALL: [MetricFormulation; 3] = [MetricFormulation::Diagonal, MetricFormulation::Coherent, MetricFormulation::Coupled];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MetricFormulation::Diagonal => "diagonal",
+            MetricFormulation::Coherent => "coherent",
+            MetricFormulation::Coupled => "coupled",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.name() == name)
+    }
+}
+
+impl fmt::Display for MetricFormulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricError {
+    CouplingOutOfRange(f32),
+    NotPositiveDefinite,
+}
+
+impl fmt::Display for MetricError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricError::CouplingOutOfRange(k) => {
+                write!(f, "metric coupling {} outside [0, {})", k, MetricModel::MAX_COUPLING)
+            }
+            MetricError::NotPositiveDefinite => write!(f, "metric is not positive definite"),
+        }
+    }
+}
+
+impl std::error::Error for MetricError {}
+
+/// The one implementation of g shared by `Tropisms` and `CSpaceEngine`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricModel {
+    pub formulation: MetricFormulation,
+    pub coupling: f32, // κ: bound on every correlation between E, H and D (`Coupled` only)
+}
+
+impl Default for MetricModel {
+    fn default() -> Self {
+        Self { formulation: MetricFormulation::default(), coupling: 0.25 }
+    }
+}
+
+impl MetricModel {
+    /// Correlations stay below 1/2, so the correlation matrix is strictly diagonally dominant
+    /// and g is positive definite wherever its diagonal is (E > 0, D > -ε)
+    pub const MAX_COUPLING: f32 = 0.5;
+    pub const EPSILON: f64 = 1e-6;
+    /// Lowest energy `clamped_tensor` evaluates g at
+    pub const MIN_ENERGY: f64 = 1e-3;
+
+    pub fn new(formulation: MetricFormulation, coupling: f32) -> Result<Self, MetricError> {
+        if !(0.0..Self::MAX_COUPLING).contains(&coupling) {
+            return Err(MetricError::CouplingOutOfRange(coupling));
+        }
+        Ok(Self { formulation, coupling })
+    }
+
+    /// g at (E, H, D). Off-diagonal terms are g_ij = c_ij √(g_ii g_jj) with
+    /// c_EH = κ tanh H, c_ED = −κ tanh D and c_HD = −κ tanh H tanh D.
+    pub fn tensor<T: RealField + Copy>(&self, energy: T, coherence: T, distortion: T) -> Matrix3<T> {
+        let one = T::one();
+        let epsilon: T = nalgebra::convert(Self::EPSILON);
+        let g_ee = one / (energy * energy);
+        let g_dd = one / (distortion + epsilon);
+        let g_hh = match self.formulation {
+            MetricFormulation::Diagonal => one / energy,
+            MetricFormulation::Coherent | MetricFormulation::Coupled => (one + coherence * coherence) / energy,
+        };
+        let mut g = Matrix3::from_diagonal(&Vector3::new(g_ee, g_hh, g_dd));
+        if self.formulation == MetricFormulation::Coupled {
+            let k: T = nalgebra::convert(self.coupling.clamp(0.0, Self::MAX_COUPLING) as f64);
+            let (th, td) = (coherence.tanh(), distortion.tanh());
+            for (i, j, c) in [(0, 1, k * th), (0, 2, -k * td), (1, 2, -k * th * td)] {
+                let v = c * (g[(i, i)] * g[(j, j)]).sqrt();
+                g[(i, j)] = v;
+                g[(j, i)] = v;
+            }
+        }
+        g
+    }
+
+    /// `tensor` with E raised to `MIN_ENERGY`, D to 0 and a non-finite H read as 0, so a starved
+    /// plant still gets a finite, positive definite g. Used wherever g is taken from a live plant.
+    pub fn clamped_tensor<T: RealField + Copy>(&self, energy: T, coherence: T, distortion: T) -> Matrix3<T> {
+        let min_energy: T = nalgebra::convert(Self::MIN_ENERGY);
+        let coherence = if coherence.is_finite() { coherence } else { T::zero() };
+        self.tensor(energy.max(min_energy), coherence, distortion.max(T::zero()))
+    }
+
+    /// `tensor`, rejected unless Cholesky succeeds
+    pub fn checked_tensor<T: RealField + Copy>(&self, energy: T, coherence: T, distortion: T) -> Result<Matrix3<T>, MetricError> {
+        let g = self.tensor(energy, coherence, distortion);
+        if is_positive_definite(&g) {
+            Ok(g)
+        } else {
+            Err(MetricError::NotPositiveDefinite)
+        }
+    }
+}
+
+/// Symmetric, finite and with a Cholesky factorization
+pub fn is_positive_definite<T: RealField + Copy>(g: &Matrix3<T>) -> bool {
+    g.iter().all(|v| v.is_finite()) && *g == g.transpose() && g.cholesky().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagonal_matches_original_metric() {
+        let g = MetricModel::default().tensor(4.0f32, 3.0, 2.0);
+        assert_eq!(g, Matrix3::new(1.0 / 16.0, 0.0, 0.0, 0.0, 0.25, 0.0, 0.0, 0.0, 1.0 / (2.0 + 1e-6)));
+        // Coherence matters in the other formulations
+        let coherent = MetricModel::new(MetricFormulation::Coherent, 0.0).unwrap();
+        assert!((coherent.tensor(4.0f32, 3.0, 2.0)[(1, 1)] - 2.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_formulations_are_positive_definite() {
+        let near_max = MetricModel::MAX_COUPLING - 1e-3;
+        for formulation in MetricFormulation::ALL {
+            let model = MetricModel::new(formulation, near_max).unwrap();
+            for e in [0.05, 1.0, 10.0, 200.0] {
+                for h in [-5.0, -0.5, 0.0, 1.0, 20.0] {
+                    for d in [0.0, 0.3, 5.0, 50.0] {
+                        assert!(model.checked_tensor(e, h, d).is_ok(), "{} at ({}, {}, {})", formulation, e, h, d);
+                    }
+                }
+            }
+            assert_eq!(model.checked_tensor(-1.0, 0.0, 0.0), Err(MetricError::NotPositiveDefinite));
+            for (e, h, d) in [(-1.0, 1.0, 0.0), (0.0, 1.0, -0.5), (-5.0, f32::NAN, f32::NAN)] {
+                assert!(is_positive_definite(&model.clamped_tensor(e, h, d)), "{} at ({}, {}, {})", formulation, e, h, d);
+            }
+        }
+        let coupled = MetricModel::new(MetricFormulation::Coupled, 0.4).unwrap().tensor(2.0, 1.0, 1.0);
+        assert!(coupled[(0, 1)] > 0.0 && coupled[(0, 2)] < 0.0 && coupled[(1, 2)] < 0.0);
+        assert_eq!(MetricModel::new(MetricFormulation::Coupled, 0.5), Err(MetricError::CouplingOutOfRange(0.5)));
+        assert_eq!(MetricFormulation::parse("coupled"), Some(MetricFormulation::Coupled));
+    }
+}
@@ -1,5 +1,11 @@
 // src/plants/mod.rs
+pub mod metric;
+pub mod nutrients;
+pub mod species;
 pub mod tropisms;
 
 // Re-export Plant and other necessary types from tropisms
-pub use tropisms::{Plant, Environment, TropismResult, Tropisms};
\ No newline at end of file
+pub use metric::{MetricFormulation, MetricModel};
+pub use nutrients::{NutrientField, NutrientLevels};
+pub use species::{Genotype, Species};
+pub use tropisms::{DynamicsParams, DynamicsState, Plant, Environment, TropismKind, TropismResult, Tropisms};
\ No newline at end of file
@@ -0,0 +1,254 @@
+// src/plants/nutrients.rs
+use nalgebra::Vector3;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Mul};
+
+/// Reserve level at which a plant is considered fully supplied with a nutrient
+pub const NUTRIENT_TARGET: f32 = 1.0;
+
+/// Amounts of the three macronutrients: nitrogen (N), phosphorus (P) and potassium (K)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct NutrientLevels {
+    pub nitrogen: f32,
+    pub phosphorus: f32,
+    pub potassium: f32,
+}
+
+impl NutrientLevels {
+    pub fn new(nitrogen: f32, phosphorus: f32, potassium: f32) -> Self {
+        Self { nitrogen, phosphorus, potassium }
+    }
+
+    /// Same amount for every nutrient
+    pub fn splat(value: f32) -> Self {
+        Self::new(value, value, value)
+    }
+
+    pub fn total(&self) -> f32 {
+        self.nitrogen + self.phosphorus + self.potassium
+    }
+
+    /// Weighted sum, used to collapse the three fields into one scalar
+    pub fn dot(&self, weights: &NutrientLevels) -> f32 {
+        self.nitrogen * weights.nitrogen
+            + self.phosphorus * weights.phosphorus
+            + self.potassium * weights.potassium
+    }
+
+    /// Applies `f` to each nutrient independently
+    pub fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        Self::new(f(self.nitrogen), f(self.phosphorus), f(self.potassium))
+    }
+
+    /// Per-nutrient supply ratio relative to `NUTRIENT_TARGET`, clamped to [0, 1]
+    pub fn sufficiency(&self) -> NutrientLevels {
+        self.map(|v| (v / NUTRIENT_TARGET).clamp(0.0, 1.0))
+    }
+
+    /// Overall nutrient status: the most limiting nutrient decides (Liebig's law of the minimum)
+    pub fn status(&self) -> f32 {
+        let s = self.sufficiency();
+        s.nitrogen.min(s.phosphorus).min(s.potassium)
+    }
+}
+
+impl Add for NutrientLevels {
+    type Output = NutrientLevels;
+
+    fn add(self, other: NutrientLevels) -> NutrientLevels {
+        NutrientLevels::new(
+            self.nitrogen + other.nitrogen,
+            self.phosphorus + other.phosphorus,
+            self.potassium + other.potassium,
+        )
+    }
+}
+
+impl AddAssign for NutrientLevels {
+    fn add_assign(&mut self, other: NutrientLevels) {
+        *self = *self + other;
+    }
+}
+
+impl Mul<f32> for NutrientLevels {
+    type Output = NutrientLevels;
+
+    fn mul(self, factor: f32) -> NutrientLevels {
+        self.map(|v| v * factor)
+    }
+}
+
+/// Scalar N, P and K fields over the soil, stored on a regular grid in the x-y plane
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NutrientField {
+    pub origin: Vector3<f32>, // World position of cell (0, 0)
+    pub cell_size: f32,
+    pub width: usize,         // Cells along x
+    pub height: usize,        // Cells along y
+    pub cells: Vec<NutrientLevels>,
+}
+
+impl NutrientField {
+    /// Creates an empty (all-zero) field
+    pub fn new(origin: Vector3<f32>, cell_size: f32, width: usize, height: usize) -> Self {
+        Self {
+            origin,
+            cell_size,
+            width,
+            height,
+            cells: vec![NutrientLevels::default(); width * height],
+        }
+    }
+
+    /// Creates a field with a low baseline and `patches` randomly placed nutrient-rich patches
+    pub fn patchy<R: Rng>(
+        rng: &mut R,
+        origin: Vector3<f32>,
+        cell_size: f32,
+        width: usize,
+        height: usize,
+        patches: usize,
+    ) -> Self {
+        let mut field = Self::new(origin, cell_size, width, height);
+        for cell in &mut field.cells {
+            *cell = NutrientLevels::splat(0.05);
+        }
+        let extent_x = width as f32 * cell_size;
+        let extent_y = height as f32 * cell_size;
+        for _ in 0..patches {
+            let center = origin + Vector3::new(
+                rng.gen_range(0.0..extent_x),
+                rng.gen_range(0.0..extent_y),
+                0.0,
+            );
+            let radius = rng.gen_range(0.5..2.5);
+            let peak = NutrientLevels::new(
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(0.0..1.0),
+            );
+            field.add_patch(center, radius, peak);
+        }
+        field
+    }
+
+    /// Adds a Gaussian patch of nutrients centred on `center`
+    pub fn add_patch(&mut self, center: Vector3<f32>, radius: f32, peak: NutrientLevels) {
+        for j in 0..self.height {
+            for i in 0..self.width {
+                let dist = (self.cell_center(i, j) - center).xy().norm();
+                let weight = (-(dist * dist) / (2.0 * radius * radius)).exp();
+                let idx = j * self.width + i;
+                self.cells[idx] += peak * weight;
+            }
+        }
+    }
+
+    /// World position of the centre of cell (i, j)
+    pub fn cell_center(&self, i: usize, j: usize) -> Vector3<f32> {
+        self.origin
+            + Vector3::new(
+                (i as f32 + 0.5) * self.cell_size,
+                (j as f32 + 0.5) * self.cell_size,
+                0.0,
+            )
+    }
+
+    fn cell_index(&self, pos: Vector3<f32>) -> Option<usize> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let local = (pos - self.origin) / self.cell_size;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let (i, j) = (local.x as usize, local.y as usize);
+        if i >= self.width || j >= self.height {
+            return None;
+        }
+        Some(j * self.width + i)
+    }
+
+    /// Bilinearly interpolated nutrient levels at `pos` (zero outside the soil grid)
+    pub fn sample(&self, pos: Vector3<f32>) -> NutrientLevels {
+        if self.width == 0 || self.height == 0 {
+            return NutrientLevels::default();
+        }
+        let local = (pos - self.origin) / self.cell_size - Vector3::new(0.5, 0.5, 0.0);
+        let max_x = (self.width - 1) as f32;
+        let max_y = (self.height - 1) as f32;
+        if local.x < -0.5 || local.y < -0.5 || local.x > max_x + 0.5 || local.y > max_y + 0.5 {
+            return NutrientLevels::default();
+        }
+        let x = local.x.clamp(0.0, max_x);
+        let y = local.y.clamp(0.0, max_y);
+        let (i0, j0) = (x.floor() as usize, y.floor() as usize);
+        let (i1, j1) = ((i0 + 1).min(self.width - 1), (j0 + 1).min(self.height - 1));
+        let (fx, fy) = (x - i0 as f32, y - j0 as f32);
+
+        let at = |i: usize, j: usize| self.cells[j * self.width + i];
+        let bottom = at(i0, j0) * (1.0 - fx) + at(i1, j0) * fx;
+        let top = at(i0, j1) * (1.0 - fx) + at(i1, j1) * fx;
+        bottom * (1.0 - fy) + top * fy
+    }
+
+    /// Gradient of the weighted nutrient scalar at `pos`, by central differences
+    pub fn gradient(&self, pos: Vector3<f32>, weights: &NutrientLevels) -> Vector3<f32> {
+        let h = self.cell_size.max(1e-3);
+        let dx = Vector3::new(h, 0.0, 0.0);
+        let dy = Vector3::new(0.0, h, 0.0);
+        let value = |p: Vector3<f32>| self.sample(p).dot(weights);
+        Vector3::new(
+            (value(pos + dx) - value(pos - dx)) / (2.0 * h),
+            (value(pos + dy) - value(pos - dy)) / (2.0 * h),
+            0.0,
+        )
+    }
+
+    /// Removes up to `demand` from the cell containing `pos`, returning what was actually taken
+    pub fn deplete(&mut self, pos: Vector3<f32>, demand: NutrientLevels) -> NutrientLevels {
+        let Some(idx) = self.cell_index(pos) else {
+            return NutrientLevels::default();
+        };
+        let cell = &mut self.cells[idx];
+        let taken = NutrientLevels::new(
+            demand.nitrogen.max(0.0).min(cell.nitrogen),
+            demand.phosphorus.max(0.0).min(cell.phosphorus),
+            demand.potassium.max(0.0).min(cell.potassium),
+        );
+        cell.nitrogen -= taken.nitrogen;
+        cell.phosphorus -= taken.phosphorus;
+        cell.potassium -= taken.potassium;
+        taken
+    }
+
+    /// Total amount of each nutrient remaining in the soil
+    pub fn totals(&self) -> NutrientLevels {
+        self.cells
+            .iter()
+            .fold(NutrientLevels::default(), |acc, cell| acc + *cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_gradient_points_to_center() {
+        let mut field = NutrientField::new(Vector3::new(-5.0, -5.0, 0.0), 0.5, 20, 10);
+        field.add_patch(Vector3::new(2.0, -2.0, 0.0), 1.5, NutrientLevels::splat(1.0));
+        let grad = field.gradient(Vector3::new(0.0, -2.0, 0.0), &NutrientLevels::splat(1.0));
+        assert!(grad.x > 0.0);
+    }
+
+    #[test]
+    fn test_deplete_is_bounded_by_available() {
+        let mut field = NutrientField::new(Vector3::new(0.0, -1.0, 0.0), 1.0, 1, 1);
+        field.cells[0] = NutrientLevels::new(0.2, 0.0, 1.0);
+        let taken = field.deplete(Vector3::new(0.5, -0.5, 0.0), NutrientLevels::splat(0.5));
+        assert_eq!(taken, NutrientLevels::new(0.2, 0.0, 0.5));
+        assert_eq!(field.cells[0], NutrientLevels::new(0.0, 0.0, 0.5));
+    }
+}
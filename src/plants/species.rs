@@ -0,0 +1,328 @@
+// src/plants/species.rs
+use nalgebra::Vector3;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use super::nutrients::NutrientLevels;
+use super::tropisms::Plant;
+
+/// Heritable parameter set that drives a plant's tropisms, energy budget and lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Genotype {
+    // Tropism sensitivities (direction change per unit stimulus and time)
+    pub phototropism: f32,
+    pub gravitropism_stem: f32,
+    pub gravitropism_root: f32,
+    pub hydrotropism: f32,
+    pub thigmotropism: f32,
+    pub chemotropism: f32,
+    // Growth rates (how far the plant advances per unit of directed growth)
+    pub stem_growth_rate: f32,
+    pub root_growth_rate: f32,
+    pub nutrient_uptake: f32,
+    // Energy yields and costs
+    pub photosynthesis_yield: f32,
+    pub water_yield: f32,
+    pub nutrient_yield: f32,
+    pub nutrient_consumption: f32,
+    pub deficiency_stress: f32, // Coherence lost per unit of nutrient deficiency
+    pub gravitropism_cost: f32,
+    pub thigmotropism_cost: f32,
+    // Lifecycle timings
+    pub seed_energy: f32,   // Energy a seedling starts with (and its parent pays)
+    pub maturity_age: f32,  // Age at which seeds can be set
+    pub seed_interval: f32, // Minimum time between two seeds
+    pub lifespan: f32,      // Age at which the plant dies
+}
+
+impl Default for Genotype {
+    fn default() -> Self {
+        Self {
+            phototropism: 0.1,
+            gravitropism_stem: 0.05,
+            gravitropism_root: 0.03,
+            hydrotropism: 0.08,
+            thigmotropism: 0.04,
+            chemotropism: 0.05,
+            stem_growth_rate: 1.0,
+            root_growth_rate: 0.5,
+            nutrient_uptake: 0.1,
+            photosynthesis_yield: 0.5,
+            water_yield: 0.3,
+            nutrient_yield: 0.05,
+            nutrient_consumption: 0.02,
+            deficiency_stress: 0.02,
+            gravitropism_cost: 0.02,
+            thigmotropism_cost: 0.03,
+            seed_energy: 10.0,
+            maturity_age: 50.0,
+            seed_interval: 25.0,
+            lifespan: 500.0,
+        }
+    }
+}
+
+impl Genotype {
+    /// Names of every parameter, in declaration order
+    pub const PARAMETERS: [&'static str; 20] = [
+        "phototropism",
+        "gravitropism_stem",
+        "gravitropism_root",
+        "hydrotropism",
+        "thigmotropism",
+        "chemotropism",
+        "stem_growth_rate",
+        "root_growth_rate",
+        "nutrient_uptake",
+        "photosynthesis_yield",
+        "water_yield",
+        "nutrient_yield",
+        "nutrient_consumption",
+        "deficiency_stress",
+        "gravitropism_cost",
+        "thigmotropism_cost",
+        "seed_energy",
+        "maturity_age",
+        "seed_interval",
+        "lifespan",
+    ];
+
+    /// The tropism sensitivities, the usual targets for optimization
+    pub const SENSITIVITIES: [&'static str; 6] = [
+        "phototropism",
+        "gravitropism_stem",
+        "gravitropism_root",
+        "hydrotropism",
+        "thigmotropism",
+        "chemotropism",
+    ];
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut f32> {
+        Some(match name {
+            "phototropism" => &mut self.phototropism,
+            "gravitropism_stem" => &mut self.gravitropism_stem,
+            "gravitropism_root" => &mut self.gravitropism_root,
+            "hydrotropism" => &mut self.hydrotropism,
+            "thigmotropism" => &mut self.thigmotropism,
+            "chemotropism" => &mut self.chemotropism,
+            "stem_growth_rate" => &mut self.stem_growth_rate,
+            "root_growth_rate" => &mut self.root_growth_rate,
+            "nutrient_uptake" => &mut self.nutrient_uptake,
+            "photosynthesis_yield" => &mut self.photosynthesis_yield,
+            "water_yield" => &mut self.water_yield,
+            "nutrient_yield" => &mut self.nutrient_yield,
+            "nutrient_consumption" => &mut self.nutrient_consumption,
+            "deficiency_stress" => &mut self.deficiency_stress,
+            "gravitropism_cost" => &mut self.gravitropism_cost,
+            "thigmotropism_cost" => &mut self.thigmotropism_cost,
+            "seed_energy" => &mut self.seed_energy,
+            "maturity_age" => &mut self.maturity_age,
+            "seed_interval" => &mut self.seed_interval,
+            "lifespan" => &mut self.lifespan,
+            _ => return None,
+        })
+    }
+
+    /// Reads a parameter by name
+    pub fn get(&self, name: &str) -> Option<f32> {
+        let mut copy = *self;
+        copy.field_mut(name).map(|v| *v)
+    }
+
+    /// Writes a parameter by name, returning false if the name is unknown
+    pub fn set(&mut self, name: &str, value: f32) -> bool {
+        match self.field_mut(name) {
+            Some(field) => {
+                *field = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Copy of this genotype with every parameter scaled by an independent factor in [1 - variation, 1 + variation]
+    pub fn mutate<R: Rng>(&self, rng: &mut R, variation: f32) -> Genotype {
+        if variation <= 0.0 {
+            return *self;
+        }
+        let mut vary = |v: f32| (v * (1.0 + rng.gen_range(-variation..variation))).max(0.0);
+        Genotype {
+            phototropism: vary(self.phototropism),
+            gravitropism_stem: vary(self.gravitropism_stem),
+            gravitropism_root: vary(self.gravitropism_root),
+            hydrotropism: vary(self.hydrotropism),
+            thigmotropism: vary(self.thigmotropism),
+            chemotropism: vary(self.chemotropism),
+            stem_growth_rate: vary(self.stem_growth_rate),
+            root_growth_rate: vary(self.root_growth_rate),
+            nutrient_uptake: vary(self.nutrient_uptake),
+            photosynthesis_yield: vary(self.photosynthesis_yield),
+            water_yield: vary(self.water_yield),
+            nutrient_yield: vary(self.nutrient_yield),
+            nutrient_consumption: vary(self.nutrient_consumption),
+            deficiency_stress: vary(self.deficiency_stress),
+            gravitropism_cost: vary(self.gravitropism_cost),
+            thigmotropism_cost: vary(self.thigmotropism_cost),
+            seed_energy: vary(self.seed_energy),
+            maturity_age: vary(self.maturity_age),
+            seed_interval: vary(self.seed_interval),
+            lifespan: vary(self.lifespan),
+        }
+    }
+}
+
+/// A named genotype plus how much it varies between generations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Species {
+    pub name: String,
+    pub description: String,
+    pub genotype: Genotype,
+    pub variation: f32, // Relative heritable variation applied to offspring
+}
+
+impl Default for Species {
+    fn default() -> Self {
+        Self {
+            name: "Common Sprout".to_string(),
+            description: "Balanced responses to light, water and nutrients".to_string(),
+            genotype: Genotype::default(),
+            variation: 0.05,
+        }
+    }
+}
+
+impl Species {
+    /// Built-in species available to every simulation
+    pub fn catalog() -> Vec<Species> {
+        let base = Genotype::default();
+        vec![
+            Species::default(),
+            Species {
+                name: "Sun Seeker".to_string(),
+                description: "Strong phototropism, expensive to maintain".to_string(),
+                genotype: Genotype {
+                    phototropism: 0.2,
+                    photosynthesis_yield: 0.6,
+                    gravitropism_cost: 0.04,
+                    ..base
+                },
+                variation: 0.05,
+            },
+            Species {
+                name: "Climbing Vine".to_string(),
+                description: "Wraps readily around obstacles and grows fast".to_string(),
+                genotype: Genotype {
+                    thigmotropism: 0.12,
+                    thigmotropism_cost: 0.015,
+                    stem_growth_rate: 1.4,
+                    gravitropism_stem: 0.03,
+                    lifespan: 350.0,
+                    ..base
+                },
+                variation: 0.08,
+            },
+            Species {
+                name: "Deep Root".to_string(),
+                description: "Roots dominate; slow to mature but long-lived".to_string(),
+                genotype: Genotype {
+                    hydrotropism: 0.14,
+                    chemotropism: 0.1,
+                    gravitropism_root: 0.06,
+                    nutrient_uptake: 0.2,
+                    water_yield: 0.4,
+                    maturity_age: 80.0,
+                    lifespan: 800.0,
+                    ..base
+                },
+                variation: 0.03,
+            },
+        ]
+    }
+
+    /// Looks up a built-in species by name
+    pub fn by_name(name: &str) -> Option<Species> {
+        Self::catalog().into_iter().find(|s| s.name == name)
+    }
+
+    /// Creates a founder plant of this species
+    pub fn instantiate(&self, id: usize, pos: Vector3<f32>) -> Plant {
+        Plant {
+            id,
+            species: self.name.clone(),
+            genotype: self.genotype,
+            pos,
+            stem_dir: Vector3::new(0.0, 1.0, 0.0), // Upward
+            root_dir: Vector3::new(0.0, -1.0, 0.0), // Downward
+            energy: self.genotype.seed_energy,
+            coherence: 1.0,
+            distortion: 0.0,
+            temporal_complexity: 0.0,
+            spatial_complexity: 0.0,
+            nutrients: NutrientLevels::splat(1.0),
+            age: 0.0,
+            last_seed_age: 0.0,
+            path: vec![pos],
+        }
+    }
+}
+
+impl Plant {
+    /// Whether the plant is old enough, rested enough and rich enough to set a seed
+    pub fn can_seed(&self) -> bool {
+        let g = &self.genotype;
+        self.age >= g.maturity_age
+            && self.age - self.last_seed_age >= g.seed_interval
+            && self.energy >= 2.0 * g.seed_energy
+    }
+
+    /// Sets a seed at `pos`: the parent pays `seed_energy` and the seedling inherits a varied genotype
+    pub fn offspring<R: Rng>(&mut self, id: usize, pos: Vector3<f32>, variation: f32, rng: &mut R) -> Plant {
+        let genotype = self.genotype.mutate(rng, variation);
+        self.energy -= self.genotype.seed_energy;
+        self.last_seed_age = self.age;
+        let species = Species { name: self.species.clone(), genotype, ..Species::default() };
+        // The seedling starts with what the parent paid, not its own mutated seed energy
+        Plant { energy: self.genotype.seed_energy, ..species.instantiate(id, pos) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_names_are_unique() {
+        let catalog = Species::catalog();
+        for (i, a) in catalog.iter().enumerate() {
+            assert!(catalog.iter().skip(i + 1).all(|b| b.name != a.name));
+        }
+        assert_eq!(Species::by_name("Deep Root").unwrap().genotype.hydrotropism, 0.14);
+    }
+
+    #[test]
+    fn test_named_parameters() {
+        let mut genotype = Genotype::default();
+        for name in Genotype::PARAMETERS {
+            assert!(genotype.get(name).is_some(), "{}", name);
+        }
+        assert!(genotype.set("hydrotropism", 0.3));
+        assert_eq!(genotype.hydrotropism, 0.3);
+        assert!(!genotype.set("wings", 1.0));
+    }
+
+    #[test]
+    fn test_offspring_inherits_with_variation() {
+        let mut rng = rand::thread_rng();
+        let species = Species::default();
+        let mut parent = species.instantiate(0, Vector3::zeros());
+        parent.age = 60.0;
+        parent.energy = 25.0;
+        assert!(parent.can_seed());
+
+        let child = parent.offspring(1, Vector3::new(1.0, 0.0, 0.0), 0.1, &mut rng);
+        assert_eq!(child.species, parent.species);
+        assert_eq!(parent.energy, 15.0);
+        assert!(!parent.can_seed()); // Must wait seed_interval
+        let ratio = child.genotype.phototropism / parent.genotype.phototropism;
+        assert!((0.9..=1.1).contains(&ratio));
+    }
+}
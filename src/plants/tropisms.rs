@@ -1,11 +1,19 @@
 // src/engines/tropisms.rs
-use nalgebra::{Vector3, Matrix3}; // For vector and matrix operations
+use nalgebra::{Vector3, Matrix3, RealField}; // For vector and matrix operations
 use rand::Rng; // For variability in environmental responses
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use super::metric::MetricModel;
+use super::nutrients::{NutrientField, NutrientLevels};
+use super::species::Genotype;
 
 
 /// Represents a plant as a computational entity in the C-Space manifold
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Plant {
+    pub id: usize,              // Stable identifier within a simulation
+    pub species: String,        // Name of the species the plant belongs to
+    pub genotype: Genotype,     // Heritable tropism, energy and lifecycle parameters
     pub pos: Vector3<f32>,      // Position in 3D space (mapped to manifold coordinates)
     pub stem_dir: Vector3<f32>, // Stem direction (coherence projection)
     pub root_dir: Vector3<f32>, // Root direction (time projection)
@@ -14,10 +22,43 @@ pub struct Plant {
     pub distortion: f32,        // D: Instability or environmental chaos
     pub temporal_complexity: f32, // T: Emergent time from growth processes
     pub spatial_complexity: f32,  // S: Structural complexity in space
+    pub nutrients: NutrientLevels, // Internal N, P, K reserves
+    pub age: f32,               // Time since germination
+    pub last_seed_age: f32,     // Age at which the last seed was set
+    pub path: Vec<Vector3<f32>>, // Positions grown through, one every `Plant::PATH_SPACING`
+}
+
+impl Plant {
+    /// Minimum distance between recorded path points
+    pub const PATH_SPACING: f32 = 0.05;
+
+    /// Most points kept in `path`; past this, every other point is dropped so long runs keep
+    /// the whole shape at a coarser spacing and per-step cost stays bounded
+    pub const MAX_PATH_POINTS: usize = 512;
+
+    /// Appends the current position to `path` once the plant has moved far enough
+    pub fn record_path(&mut self) {
+        match self.path.last() {
+            Some(last) if (self.pos - last).norm() < Self::PATH_SPACING => {}
+            _ => {
+                self.path.push(self.pos);
+                if self.path.len() > Self::MAX_PATH_POINTS {
+                    // Keep the start, the newest point and every even index in between
+                    let last = self.path.len() - 1;
+                    let mut i = 0;
+                    self.path.retain(|_| {
+                        let keep = i % 2 == 0 || i == last;
+                        i += 1;
+                        keep
+                    });
+                }
+            }
+        }
+    }
 }
 
 /// Represents the environment as a computational manifold
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
     pub light_pos: Vector3<f32>,  // Light source position
     pub water_pos: Vector3<f32>,  // Water source position
@@ -26,59 +67,195 @@ pub struct Environment {
     pub light_intensity: f32,     // Energy contribution from light
     pub water_level: f32,         // Energy contribution from water
     pub metric_tensor: Matrix3<f32>, // g: Defines manifold geometry
+    pub metric: MetricModel,      // How g is built from (E, H, D)
     pub d_critical: f32,          // Critical distortion threshold
+    pub alpha: f32,               // Scaling coefficient from coherence dynamics
+    pub beta: f32,                // Scaling coefficient from distortion dynamics
+    pub nutrients: NutrientField, // Soil N, P, K fields
+}
+
+/// Which tropism produced a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TropismKind {
+    Phototropism,
+    Gravitropism,
+    Hydrotropism,
+    Thigmotropism,
+    Chemotropism,
+}
+
+impl TropismKind {
+    /// All kinds, in the order `Tropisms::apply_all` applies them
+    pub const ALL: [TropismKind; 5] = [
+        TropismKind::Phototropism,
+        TropismKind::Gravitropism,
+        TropismKind::Hydrotropism,
+        TropismKind::Thigmotropism,
+        TropismKind::Chemotropism,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TropismKind::Phototropism => "Phototropism",
+            TropismKind::Gravitropism => "Gravitropism",
+            TropismKind::Hydrotropism => "Hydrotropism",
+            TropismKind::Thigmotropism => "Thigmotropism",
+            TropismKind::Chemotropism => "Chemotropism",
+        }
+    }
+}
+
+impl fmt::Display for TropismKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 /// Tropism result for logging and rendering
 #[derive(Debug, Clone)]
 pub struct TropismResult {
+    pub kind: TropismKind,
+    pub plant_id: usize,
+    pub step: u64,                  // Simulation step the tropism was applied in
     pub growth_delta: Vector3<f32>, // Change in position or direction
+    pub root_delta: Vector3<f32>,   // Root growth when it differs from `growth_delta` (gravitropism), else zero
+    pub contact: bool,              // Thigmotropism found an obstacle in range
     pub rho_c: f32,                 // Complex density after tropism
-    pub log: String,                // Description for dev_window
+    pub energy_delta: f32,          // Change in E caused by this tropism
+    pub coherence: f32,             // H after tropism
+    pub distortion: f32,            // D after tropism
+    pub temporal_complexity: f32,   // T after tropism
+    pub uptake: NutrientLevels,     // Nutrients taken up from the soil
+}
+
+impl fmt::Display for TropismResult {
+    /// One-line description for dev_window
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let growth = self.growth_delta.norm();
+        match self.kind {
+            TropismKind::Phototropism => write!(f, "Phototropism: Grew {:.2} toward light", growth)?,
+            TropismKind::Gravitropism => {
+                write!(f, "Gravitropism: Stem up {:.2}, roots down {:.2}", growth, self.root_delta.norm())?
+            }
+            TropismKind::Hydrotropism => write!(f, "Hydrotropism: Roots grew {:.2} toward water", growth)?,
+            TropismKind::Thigmotropism if !self.contact => {
+                return write!(f, "Thigmotropism: No obstacles in range");
+            }
+            TropismKind::Thigmotropism => write!(f, "Thigmotropism: Wrapped {:.2} around obstacle", growth)?,
+            TropismKind::Chemotropism => write!(
+                f,
+                "Chemotropism: Roots grew {:.2} up nutrient gradient, uptake N={:.3} P={:.3} K={:.3}",
+                growth, self.uptake.nitrogen, self.uptake.phosphorus, self.uptake.potassium
+            )?,
+        }
+        write!(
+            f,
+            ", ρ_c={:.2}, H={:.2}, D={:.2}, T={:.2}",
+            self.rho_c, self.coherence, self.distortion, self.temporal_complexity
+        )
+    }
+}
+
+/// Coherence, distortion and temporal complexity: the state advanced by `Tropisms::dynamics_step`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynamicsState<T> {
+    pub coherence: T,           // H
+    pub distortion: T,          // D
+    pub temporal_complexity: T, // T
+}
+
+impl DynamicsState<f32> {
+    pub fn of(plant: &Plant) -> Self {
+        Self {
+            coherence: plant.coherence,
+            distortion: plant.distortion,
+            temporal_complexity: plant.temporal_complexity,
+        }
+    }
+}
+
+/// Quantities held fixed during one dynamics step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynamicsParams<T> {
+    pub alpha: T,  // Coherence decay rate
+    pub beta: T,   // Distortion / time generation rate
+    pub energy: T, // E
+    pub grad_s: T, // Spatial complexity gradient (mean distance to resources)
 }
 
 #[derive(Debug, Clone)]
 pub struct Tropisms;
 
 impl Tropisms {
-    /// Computes the metric tensor based on energy and distortion
-    fn compute_metric_tensor(energy: f32, distortion: f32) -> Matrix3<f32> {
-        let epsilon = 1e-6;
-        Matrix3::new(
-            1.0 / (energy * energy), 0.0, 0.0,
-            0.0, 1.0 / energy, 0.0,
-            0.0, 0.0, 1.0 / (distortion + epsilon),
-        )
-    }
-
     /// Computes complex density: ρ_c = sqrt(S^2 + T^2) * E
-    fn compute_complex_density(spatial: f32, temporal: f32, energy: f32) -> f32 {
+    pub fn compute_complex_density(spatial: f32, temporal: f32, energy: f32) -> f32 {
         (spatial * spatial + temporal * temporal).sqrt() * energy
     }
 
-    /// Updates coherence and distortion dynamics
-    fn update_dynamics(plant: &mut Plant, env: &Environment, dt: f32) {
-        let alpha = 0.05; // Scaling coefficient from coherence dynamics
-        let beta = 0.1;   // Scaling coefficient from distortion dynamics
-        let epsilon = 1e-6;
+    /// Builds a result from the plant state after a tropism has been applied
+    fn result(kind: TropismKind, plant: &Plant, growth: Vector3<f32>, energy_before: f32) -> TropismResult {
+        TropismResult {
+            kind,
+            plant_id: plant.id,
+            step: 0, // Stamped by PlantEngine
+            growth_delta: growth,
+            root_delta: Vector3::zeros(),
+            contact: false,
+            rho_c: Self::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy),
+            energy_delta: plant.energy - energy_before,
+            coherence: plant.coherence,
+            distortion: plant.distortion,
+            temporal_complexity: plant.temporal_complexity,
+            uptake: NutrientLevels::default(),
+        }
+    }
 
+    /// Inputs to the H–D–T equations for `plant` in `env`
+    pub fn dynamics_params(plant: &Plant, env: &Environment) -> DynamicsParams<f32> {
         // Simplified spatial complexity gradient (distance to nearest resource)
         let light_dist = (env.light_pos - plant.pos).norm();
         let water_dist = (env.water_pos - plant.pos).norm();
-        let grad_s = (light_dist + water_dist) / 2.0;
+        DynamicsParams {
+            alpha: env.alpha,
+            beta: env.beta,
+            energy: plant.energy,
+            grad_s: (light_dist + water_dist) / 2.0,
+        }
+    }
+
+    /// One explicit step of the coupled H–D–T equations. Generic so analysis code can run the
+    /// engine's own equations in f64.
+    pub fn dynamics_step<T: RealField + Copy>(state: DynamicsState<T>, params: DynamicsParams<T>, dt: T) -> DynamicsState<T> {
+        let epsilon: T = nalgebra::convert(1e-6);
+        let one = T::one();
+        let DynamicsParams { alpha, beta, energy, grad_s } = params;
 
         // Coherence evolution: dH/dt = -α (D/(H+ε) + ∇S)
-        let d_h_dt = -alpha * (plant.distortion / (plant.coherence + epsilon) + grad_s);
+        let d_h_dt = -alpha * (state.distortion / (state.coherence + epsilon) + grad_s);
         let delta_h = d_h_dt * dt;
 
         // Distortion evolution: dD/dt = β * log(1 + |ΔH| * E)
-        let d_d_dt = beta * (1.0 + (delta_h.abs() * plant.energy)).ln();
-        plant.distortion += d_d_dt * dt;
-        plant.coherence += delta_h;
+        let d_d_dt = beta * (one + delta_h.abs() * energy).ln();
+        let distortion = state.distortion + d_d_dt * dt;
+        let coherence = state.coherence + delta_h;
 
         // Emergent time evolution (Perpendicularity Mechanics): dT/dt = β * tanh(|ΔH| * E) * sign(H)
-        let d_t_dt = beta * (delta_h.abs() * plant.energy).tanh() * plant.coherence.signum();
-        plant.temporal_complexity += d_t_dt * dt;
+        let sign = if coherence.is_sign_negative() { -one } else { one };
+        let d_t_dt = beta * (delta_h.abs() * energy).tanh() * sign;
+
+        DynamicsState {
+            coherence,
+            distortion,
+            temporal_complexity: state.temporal_complexity + d_t_dt * dt,
+        }
+    }
+
+    /// Updates coherence and distortion dynamics
+    fn update_dynamics(plant: &mut Plant, env: &Environment, dt: f32) {
+        let next = Self::dynamics_step(DynamicsState::of(plant), Self::dynamics_params(plant, env), dt);
+        plant.coherence = next.coherence;
+        plant.distortion = next.distortion;
+        plant.temporal_complexity = next.temporal_complexity;
 
         // Update spatial complexity based on growth extent
         plant.spatial_complexity = (plant.stem_dir.norm() + plant.root_dir.norm()) / 2.0;
@@ -93,76 +270,59 @@ impl Tropisms {
 
     /// Phototropism: Growth toward light, optimizing geodesic path
     pub fn phototropism(plant: &mut Plant, env: &Environment, dt: f32) -> TropismResult {
+        let energy_before = plant.energy;
+        let g = plant.genotype;
         let light_dir = (env.light_pos - plant.pos).normalize();
-        let intensity_factor = env.light_intensity * 0.1;
+        let intensity_factor = env.light_intensity * g.phototropism;
         let growth = light_dir * intensity_factor * dt;
 
         plant.stem_dir = (plant.stem_dir + growth).normalize();
-        plant.pos += growth;
-        plant.energy += intensity_factor * 0.5 * dt;
+        plant.pos += growth * g.stem_growth_rate;
+        plant.energy += intensity_factor * g.photosynthesis_yield * dt;
 
         Self::update_dynamics(plant, env, dt);
-        let rho_c = Self::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy);
-
-        TropismResult {
-            growth_delta: growth,
-            rho_c,
-            log: format!(
-                "Phototropism: Grew {:.2} toward light, ρ_c={:.2}, H={:.2}, D={:.2}, T={:.2}",
-                growth.norm(), rho_c, plant.coherence, plant.distortion, plant.temporal_complexity
-            ),
-        }
+        Self::result(TropismKind::Phototropism, plant, growth, energy_before)
     }
 
     /// Gravitropism: Stems up, roots down, aligning with manifold curvature
     pub fn gravitropism(plant: &mut Plant, env: &Environment, dt: f32) -> TropismResult {
+        let energy_before = plant.energy;
+        let g = plant.genotype;
         let gravity_dir = env.gravity.normalize();
-        let stem_growth = -gravity_dir * 0.05 * dt;
-        let root_growth = gravity_dir * 0.03 * dt;
+        let stem_growth = -gravity_dir * g.gravitropism_stem * dt;
+        let root_growth = gravity_dir * g.gravitropism_root * dt;
 
         plant.stem_dir = (plant.stem_dir + stem_growth).normalize();
         plant.root_dir = (plant.root_dir + root_growth).normalize();
-        plant.pos += stem_growth;
-        plant.energy -= 0.02 * dt;
+        plant.pos += stem_growth * g.stem_growth_rate;
+        plant.energy -= g.gravitropism_cost * dt;
 
         Self::update_dynamics(plant, env, dt);
-        let rho_c = Self::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy);
-
         TropismResult {
-            growth_delta: stem_growth,
-            rho_c,
-            log: format!(
-                "Gravitropism: Stem up {:.2}, roots down {:.2}, ρ_c={:.2}, H={:.2}, D={:.2}, T={:.2}",
-                stem_growth.norm(), root_growth.norm(), rho_c, plant.coherence, plant.distortion, plant.temporal_complexity
-            ),
+            root_delta: root_growth,
+            ..Self::result(TropismKind::Gravitropism, plant, stem_growth, energy_before)
         }
     }
 
     /// Hydrotropism: Roots toward water, navigating energy gradients
     pub fn hydrotropism(plant: &mut Plant, env: &Environment, dt: f32) -> TropismResult {
+        let energy_before = plant.energy;
+        let g = plant.genotype;
         let water_dir = (env.water_pos - plant.pos).normalize();
-        let water_factor = env.water_level * 0.08 * dt;
+        let water_factor = env.water_level * g.hydrotropism * dt;
         let growth = water_dir * water_factor;
 
         plant.root_dir = (plant.root_dir + growth).normalize();
-        plant.pos += growth * 0.5;
-        plant.energy += water_factor * 0.3;
+        plant.pos += growth * g.root_growth_rate;
+        plant.energy += water_factor * g.water_yield;
 
         Self::update_dynamics(plant, env, dt);
-        let rho_c = Self::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy);
-
-        TropismResult {
-            growth_delta: growth,
-            rho_c,
-            log: format!(
-                "Hydrotropism: Roots grew {:.2} toward water, ρ_c={:.2}, H={:.2}, D={:.2}, T={:.2}",
-                growth.norm(), rho_c, plant.coherence, plant.distortion, plant.temporal_complexity
-            ),
-        }
+        Self::result(TropismKind::Hydrotropism, plant, growth, energy_before)
     }
 
     /// Thigmotropism: Wrapping around obstacles, adapting manifold topology
     pub fn thigmotropism(plant: &mut Plant, env: &Environment, dt: f32) -> TropismResult {
+        let energy_before = plant.energy;
         let mut closest_obstacle = None;
         let mut min_dist = f32::MAX;
 
@@ -174,49 +334,73 @@ impl Tropisms {
             }
         }
 
-        let result = if let Some(obstacle) = closest_obstacle {
+        if let Some(obstacle) = closest_obstacle {
             let contact_dir = (obstacle - plant.pos).normalize();
             let tangent = Vector3::new(-contact_dir.y, contact_dir.x, 0.0).normalize();
-            let growth = tangent * 0.04 * dt;
+            let g = plant.genotype;
+            let growth = tangent * g.thigmotropism * dt;
 
             plant.stem_dir = (plant.stem_dir + growth).normalize();
-            plant.pos += growth;
-            plant.energy -= 0.03 * dt;
+            plant.pos += growth * g.stem_growth_rate;
+            plant.energy -= g.thigmotropism_cost * dt;
 
             Self::update_dynamics(plant, env, dt);
-            let rho_c = Self::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy);
-
             TropismResult {
-                growth_delta: growth,
-                rho_c,
-                log: format!(
-                    "Thigmotropism: Wrapped {:.2} around obstacle, ρ_c={:.2}, H={:.2}, D={:.2}, T={:.2}",
-                    growth.norm(), rho_c, plant.coherence, plant.distortion, plant.temporal_complexity
-                ),
+                contact: true,
+                ..Self::result(TropismKind::Thigmotropism, plant, growth, energy_before)
             }
         } else {
-            TropismResult {
-                growth_delta: Vector3::zeros(),
-                rho_c: Self::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy),
-                log: "Thigmotropism: No obstacles in range".to_string(),
-            }
+            Self::result(TropismKind::Thigmotropism, plant, Vector3::zeros(), energy_before)
+        }
+    }
+
+    /// Chemotropism: Roots climb nutrient gradients, drawing N, P and K from the soil
+    pub fn chemotropism(plant: &mut Plant, env: &mut Environment, dt: f32) -> TropismResult {
+        let energy_before = plant.energy;
+        let g = plant.genotype;
+        let root_tip = plant.pos + plant.root_dir;
+
+        // Steer toward whichever nutrients the plant is short of
+        let deficit = plant.nutrients.sufficiency().map(|s| (1.0 - s).max(0.1));
+        let gradient = env.nutrients.gradient(root_tip, &deficit);
+        let growth = if gradient.norm() > 1e-6 {
+            gradient.normalize() * g.chemotropism * dt
+        } else {
+            Vector3::zeros()
         };
 
-        result
+        plant.root_dir = (plant.root_dir + growth).normalize();
+        plant.pos += growth * g.root_growth_rate;
+
+        // Uptake depletes the soil; metabolism consumes reserves
+        let uptake = env.nutrients.deplete(root_tip, deficit * (g.nutrient_uptake * dt));
+        plant.nutrients += uptake;
+        plant.nutrients = plant.nutrients.map(|v| (v - g.nutrient_consumption * dt).max(0.0));
+
+        // Well-supplied plants gain energy; deficiency erodes coherence
+        let status = plant.nutrients.status();
+        plant.energy += g.nutrient_yield * status * dt;
+        plant.coherence -= g.deficiency_stress * (1.0 - status) * dt;
+
+        Self::update_dynamics(plant, env, dt);
+        TropismResult {
+            uptake,
+            ..Self::result(TropismKind::Chemotropism, plant, growth, energy_before)
+        }
     }
 
     /// Apply all tropisms, simulating navigation through the manifold
-    pub fn apply_all(plant: &mut Plant, env: &mut Environment, dt: f32) -> Vec<TropismResult> {
-        let mut rng = rand::thread_rng();
-        let mut results = Vec::new();
-
-        results.push(Self::phototropism(plant, env, dt));
-        results.push(Self::gravitropism(plant, env, dt));
-        results.push(Self::hydrotropism(plant, env, dt));
-        results.push(Self::thigmotropism(plant, env, dt));
+    pub fn apply_all<R: Rng>(plant: &mut Plant, env: &mut Environment, dt: f32, rng: &mut R) -> Vec<TropismResult> {
+        let results = vec![
+            Self::phototropism(plant, env, dt),
+            Self::gravitropism(plant, env, dt),
+            Self::hydrotropism(plant, env, dt),
+            Self::thigmotropism(plant, env, dt),
+            Self::chemotropism(plant, env, dt),
+        ];
 
         // Update metric tensor based on current state
-        env.metric_tensor = Self::compute_metric_tensor(plant.energy, plant.distortion);
+        env.metric_tensor = env.metric.clamped_tensor(plant.energy, plant.coherence, plant.distortion);
 
         // Add noise to simulate manifold fluctuations
         let noise = Vector3::new(
@@ -233,28 +417,14 @@ impl Tropisms {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plants::species::Species;
+    use crate::simulation::scenario::Scenario;
 
+    /// A default-species plant in the bundled default scene, on bare soil
     fn setup() -> (Plant, Environment) {
-        let plant = Plant {
-            pos: Vector3::new(0.0, 0.0, 0.0),
-            stem_dir: Vector3::new(0.0, 1.0, 0.0),
-            root_dir: Vector3::new(0.0, -1.0, 0.0),
-            energy: 10.0,
-            coherence: 1.0,
-            distortion: 0.0,
-            temporal_complexity: 0.0,
-            spatial_complexity: 0.0,
-        };
-        let env = Environment {
-            light_pos: Vector3::new(5.0, 5.0, 0.0),
-            water_pos: Vector3::new(2.0, -2.0, 0.0),
-            gravity: Vector3::new(0.0, -1.0, 0.0),
-            obstacles: vec![Vector3::new(1.0, 0.0, 0.0)],
-            light_intensity: 1.0,
-            water_level: 1.0,
-            metric_tensor: Matrix3::identity(),
-            d_critical: 10.0,
-        };
+        let plant = Species::default().instantiate(0, Vector3::new(0.0, 0.0, 0.0));
+        let mut env = Scenario::example("default").unwrap().build().unwrap().environment;
+        env.nutrients = NutrientField::default();
         (plant, env)
     }
 
@@ -265,14 +435,61 @@ mod tests {
         assert!(result.growth_delta.norm() > 0.0);
         assert!(plant.energy > 10.0);
         assert!(result.rho_c > 0.0);
+        assert_eq!(result.kind, TropismKind::Phototropism);
+        assert!(result.energy_delta > 0.0);
+        assert_eq!(result.coherence, plant.coherence);
+    }
+
+    #[test]
+    fn test_result_descriptions() {
+        let (mut plant, mut env) = setup();
+        let gravitropism = Tropisms::gravitropism(&mut plant, &env, 1.0);
+        assert!(gravitropism.root_delta.norm() > 0.0);
+        assert!(gravitropism.to_string().contains(&format!("roots down {:.2}", gravitropism.root_delta.norm())));
+
+        // An obstacle in range is reported even when the plant does not wrap around it
+        env.obstacles = vec![plant.pos + Vector3::new(0.5, 0.0, 0.0)];
+        plant.genotype.thigmotropism = 0.0;
+        let touching = Tropisms::thigmotropism(&mut plant, &env, 1.0);
+        assert!(touching.contact);
+        assert!(touching.to_string().starts_with("Thigmotropism: Wrapped 0.00 around obstacle"));
+        env.obstacles.clear();
+        assert_eq!(Tropisms::thigmotropism(&mut plant, &env, 1.0).to_string(), "Thigmotropism: No obstacles in range");
     }
 
     #[test]
     fn test_singularity() {
         let (mut plant, mut env) = setup();
         plant.distortion = 15.0; // Exceed d_critical
-        let results = Tropisms::apply_all(&mut plant, &mut env, 1.0);
+        let results = Tropisms::apply_all(&mut plant, &mut env, 1.0, &mut rand::thread_rng());
         assert_eq!(plant.coherence, 0.0); // Pure Time State
+        assert!(results.iter().all(|r| r.coherence == 0.0));
         assert_eq!(plant.spatial_complexity, 0.0);
     }
+
+    #[test]
+    fn test_path_is_capped() {
+        let (mut plant, _) = setup();
+        for i in 1..=2000 {
+            plant.pos = Vector3::new(i as f32 * 2.0 * Plant::PATH_SPACING, 0.0, 0.0);
+            plant.record_path();
+        }
+        assert!(plant.path.len() <= Plant::MAX_PATH_POINTS);
+        assert_eq!(plant.path[0], Vector3::zeros());
+        assert_eq!(*plant.path.last().unwrap(), plant.pos);
+    }
+
+    #[test]
+    fn test_chemotropism_uptake() {
+        let (mut plant, mut env) = setup();
+        plant.nutrients = NutrientLevels::default();
+        env.nutrients = NutrientField::new(Vector3::new(-5.0, -5.0, 0.0), 0.5, 20, 10);
+        env.nutrients.add_patch(Vector3::new(2.0, -1.0, 0.0), 1.5, NutrientLevels::splat(1.0));
+        let before = env.nutrients.totals();
+
+        let result = Tropisms::chemotropism(&mut plant, &mut env, 1.0);
+        assert!(result.growth_delta.x > 0.0); // Roots turn toward the patch
+        assert!(result.uptake.nitrogen > 0.0);
+        assert!(env.nutrients.totals().nitrogen < before.nitrogen); // Soil depleted
+    }
 }
\ No newline at end of file
@@ -0,0 +1,235 @@
+// src/render/apng.rs
+use std::fmt;
+use std::io::{self, Cursor, Seek, SeekFrom, Write};
+
+const SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApngError {
+    NoFrames,
+    NotAPng(usize),         // Index of the frame that is not a PNG
+    Truncated(usize),       // Index of the frame whose chunks run past its end
+    MismatchedFrame(usize), // Index of a frame whose size or pixel format differs from the first
+    Io(String),             // The animation could not be written
+}
+
+impl fmt::Display for ApngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApngError::NoFrames => write!(f, "no frames to animate"),
+            ApngError::NotAPng(i) => write!(f, "frame {} is not a PNG image", i),
+            ApngError::Truncated(i) => write!(f, "frame {} is truncated", i),
+            ApngError::MismatchedFrame(i) => write!(f, "frame {} differs in size or format from the first", i),
+            ApngError::Io(reason) => write!(f, "cannot write animation: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ApngError {}
+
+impl From<io::Error> for ApngError {
+    fn from(e: io::Error) -> Self {
+        ApngError::Io(e.to_string())
+    }
+}
+
+/// Writes same-sized PNG images one by one into a looping animated PNG shown at `fps` frames
+/// per second, so only the frame being added is held in memory. Each frame's compressed pixel
+/// data is reused as is; viewers without APNG support show the first frame. `finish` writes
+/// the end of the file and patches the frame count into the header.
+pub struct ApngWriter<W: Write + Seek> {
+    out: W,
+    fps: u16,
+    header: Option<Vec<u8>>, // IHDR of the first frame, which every frame must match
+    control_at: u64,         // Where the acTL chunk holding the frame count starts
+    frames: usize,
+    sequence: u32, // Shared by fcTL and fdAT chunks, starting at zero
+}
+
+impl<W: Write + Seek> ApngWriter<W> {
+    pub fn new(out: W, fps: u16) -> Self {
+        Self { out, fps, header: None, control_at: 0, frames: 0, sequence: 0 }
+    }
+
+    /// Frames added so far
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Appends one PNG image; nothing is written if it is rejected
+    pub fn add_frame(&mut self, png: &[u8]) -> Result<(), ApngError> {
+        let index = self.frames;
+        let chunks = chunks(png, index)?;
+        let header = chunks
+            .iter()
+            .find(|(kind, _)| kind == b"IHDR")
+            .map(|(_, data)| *data)
+            .ok_or(ApngError::NotAPng(index))?;
+        let mut out = Vec::new();
+        match &self.header {
+            Some(first) if first != header => return Err(ApngError::MismatchedFrame(index)),
+            Some(_) => {}
+            None => {
+                out.extend_from_slice(SIGNATURE);
+                write_chunk(&mut out, b"IHDR", header);
+                self.control_at = self.out.stream_position()? + out.len() as u64;
+                write_chunk(&mut out, b"acTL", &animation_control(0));
+            }
+        }
+
+        let mut frame_control = self.sequence.to_be_bytes().to_vec();
+        frame_control.extend_from_slice(&header[0..8]); // Width and height
+        frame_control.extend_from_slice(&[0; 8]); // x and y offsets
+        frame_control.extend_from_slice(&1u16.to_be_bytes());
+        frame_control.extend_from_slice(&self.fps.max(1).to_be_bytes());
+        frame_control.extend_from_slice(&[0, 0]); // Leave the frame in place, replace every pixel
+        write_chunk(&mut out, b"fcTL", &frame_control);
+        self.sequence += 1;
+
+        for (_, data) in chunks.iter().filter(|(kind, _)| kind == b"IDAT") {
+            if index == 0 {
+                write_chunk(&mut out, b"IDAT", data);
+            } else {
+                let mut frame_data = self.sequence.to_be_bytes().to_vec();
+                frame_data.extend_from_slice(data);
+                write_chunk(&mut out, b"fdAT", &frame_data);
+                self.sequence += 1;
+            }
+        }
+        self.out.write_all(&out)?;
+        self.header.get_or_insert_with(|| header.to_vec());
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Ends the file and fills in the frame count; returns the underlying writer
+    pub fn finish(mut self) -> Result<W, ApngError> {
+        if self.frames == 0 {
+            return Err(ApngError::NoFrames);
+        }
+        let mut end = Vec::new();
+        write_chunk(&mut end, b"IEND", &[]);
+        self.out.write_all(&end)?;
+        let mut control = Vec::new();
+        write_chunk(&mut control, b"acTL", &animation_control(self.frames as u32));
+        self.out.seek(SeekFrom::Start(self.control_at))?;
+        self.out.write_all(&control)?;
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// Joins same-sized PNG images into one looping animated PNG held in memory
+pub fn assemble(frames: &[Vec<u8>], fps: u16) -> Result<Vec<u8>, ApngError> {
+    let mut writer = ApngWriter::new(Cursor::new(Vec::new()), fps);
+    for frame in frames {
+        writer.add_frame(frame)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// acTL data: the frame count, then zero plays for looping forever
+fn animation_control(frames: u32) -> Vec<u8> {
+    let mut control = frames.to_be_bytes().to_vec();
+    control.extend_from_slice(&0u32.to_be_bytes());
+    control
+}
+
+/// A chunk's type and data
+type Chunk<'a> = ([u8; 4], &'a [u8]);
+
+/// Every chunk of PNG `bytes`
+fn chunks(bytes: &[u8], index: usize) -> Result<Vec<Chunk<'_>>, ApngError> {
+    let body = bytes.strip_prefix(SIGNATURE).ok_or(ApngError::NotAPng(index))?;
+    let mut chunks = Vec::new();
+    let mut at = 0;
+    while at < body.len() {
+        let header = body.get(at..at + 8).ok_or(ApngError::Truncated(index))?;
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = [header[4], header[5], header[6], header[7]];
+        let data = body.get(at + 8..at + 8 + length).ok_or(ApngError::Truncated(index))?;
+        chunks.push((kind, data));
+        at += 12 + length; // Length, type, data and CRC
+        if &kind == b"IEND" {
+            break;
+        }
+    }
+    Ok(chunks)
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 as PNG uses it (ISO 3309, reflected, polynomial 0xEDB88320)
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A PNG skeleton: only the chunk layout matters to `assemble`
+    fn png(width: u32, pixels: &[&[u8]]) -> Vec<u8> {
+        let mut out = SIGNATURE.to_vec();
+        let mut header = width.to_be_bytes().to_vec();
+        header.extend_from_slice(&1u32.to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        write_chunk(&mut out, b"IHDR", &header);
+        for data in pixels {
+            write_chunk(&mut out, b"IDAT", data);
+        }
+        write_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    #[test]
+    fn test_assemble_apng() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        let frames = [png(2, &[b"aa"]), png(2, &[b"bb", b"cc"]), png(2, &[b"dd"])];
+        let apng = assemble(&frames, 12).unwrap();
+
+        let parsed = chunks(&apng, 0).unwrap();
+        let kinds: Vec<&[u8]> = parsed.iter().map(|(kind, _)| &kind[..]).collect();
+        let expected: [&[u8]; 10] =
+            [b"IHDR", b"acTL", b"fcTL", b"IDAT", b"fcTL", b"fdAT", b"fdAT", b"fcTL", b"fdAT", b"IEND"];
+        assert_eq!(kinds, expected);
+        assert_eq!(&parsed[1].1[..4], &3u32.to_be_bytes());
+        // fcTL and fdAT share one sequence, starting at zero
+        let sequence: Vec<u32> = parsed[2..9]
+            .iter()
+            .filter(|(kind, _)| kind != b"IDAT")
+            .map(|(_, data)| u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+            .collect();
+        assert_eq!(sequence, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(&parsed[5].1[4..], b"bb");
+        assert_eq!(&parsed[4].1[22..24], &12u16.to_be_bytes());
+
+        // Streamed frame by frame, a rejected frame writes nothing
+        let mut writer = ApngWriter::new(Cursor::new(Vec::new()), 12);
+        writer.add_frame(&frames[0]).unwrap();
+        assert_eq!(writer.add_frame(&png(3, &[b"xx"])), Err(ApngError::MismatchedFrame(1)));
+        writer.add_frame(&frames[1]).unwrap();
+        writer.add_frame(&frames[2]).unwrap();
+        assert_eq!(writer.frames(), 3);
+        assert_eq!(writer.finish().unwrap().into_inner(), apng);
+
+        assert_eq!(assemble(&[], 10), Err(ApngError::NoFrames));
+        assert_eq!(assemble(&[png(2, &[b"aa"]), png(3, &[b"bb"])], 10), Err(ApngError::MismatchedFrame(1)));
+        assert_eq!(assemble(&[b"GIF89a".to_vec()], 10), Err(ApngError::NotAPng(0)));
+    }
+}
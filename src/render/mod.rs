@@ -0,0 +1,11 @@
+// src/render/mod.rs
+pub mod apng;
+#[cfg(feature = "render")]
+pub mod scene;
+#[cfg(feature = "render")]
+pub mod timelapse;
+
+#[cfg(feature = "render")]
+pub use scene::{draw_scene, FrameRenderer, Scene};
+#[cfg(feature = "render")]
+pub use timelapse::{TimeLapse, TimeLapseConfig};
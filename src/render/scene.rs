@@ -0,0 +1,275 @@
+// src/render/scene.rs
+use std::fmt;
+use std::io;
+use crate::engines::snapshot::{CSpaceSnapshot, FieldKind, ScalarField};
+use crate::plants::tropisms::{Environment, Plant};
+use crate::simulation::simulation_env::SimulationEnv;
+use super::apng::ApngError;
+
+/// Pixels per world unit
+pub const SCALE: f64 = 20.0;
+
+/// Viridis control points, evenly spaced over [0, 1]
+const VIRIDIS: [(f64, f64, f64); 5] = [
+    (0.267, 0.005, 0.329),
+    (0.229, 0.322, 0.546),
+    (0.128, 0.567, 0.551),
+    (0.369, 0.789, 0.383),
+    (0.993, 0.906, 0.144),
+];
+
+/// Viridis colour for `t` in [0, 1] (clamped)
+pub fn colormap(t: f64) -> (f64, f64, f64) {
+    let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+    let x = t * (VIRIDIS.len() - 1) as f64;
+    let i = (x.floor() as usize).min(VIRIDIS.len() - 2);
+    let f = x - i as f64;
+    let (a, b) = (VIRIDIS[i], VIRIDIS[i + 1]);
+    (a.0 + (b.0 - a.0) * f, a.1 + (b.1 - a.1) * f, a.2 + (b.2 - a.2) * f)
+}
+
+/// Display settings for one field
+#[derive(Debug, Clone)]
+pub struct LayerSettings {
+    pub kind: FieldKind,
+    pub enabled: bool,
+    pub auto_range: bool, // Follow the field's own min and max each frame
+    pub min: f32,
+    pub max: f32,
+}
+
+impl LayerSettings {
+    /// Colour range to use for `field`; in auto mode this also remembers it as the manual range
+    pub fn range(&mut self, field: &ScalarField) -> (f32, f32) {
+        if self.auto_range {
+            let (lo, hi) = field.range();
+            if lo.is_finite() && hi.is_finite() {
+                self.min = lo;
+                self.max = hi;
+            }
+        }
+        (self.min, self.max)
+    }
+}
+
+/// Which fields are drawn behind the plants, and how
+#[derive(Debug, Clone)]
+pub struct OverlaySettings {
+    pub layers: Vec<LayerSettings>, // One per `FieldKind::ALL`, drawn in that order
+    pub opacity: f64,
+}
+
+impl Default for OverlaySettings {
+    fn default() -> Self {
+        Self {
+            layers: FieldKind::ALL
+                .iter()
+                .map(|&kind| LayerSettings {
+                    kind,
+                    enabled: kind == FieldKind::ComplexDensity,
+                    auto_range: true,
+                    min: 0.0,
+                    max: 1.0,
+                })
+                .collect(),
+            opacity: 0.6,
+        }
+    }
+}
+
+/// Fills each cell of `field` with its colour. `to_screen` maps a world position to pixels and
+/// `scale` is pixels per world unit.
+pub fn draw_field<F: Fn(f32, f32) -> (f64, f64)>(
+    cr: &cairo::Context,
+    field: &ScalarField,
+    (min, max): (f32, f32),
+    opacity: f64,
+    scale: f64,
+    to_screen: F,
+) -> Result<(), cairo::Error> {
+    let span = if max > min { (max - min) as f64 } else { 1.0 };
+    let size = field.cell_size as f64 * scale;
+    for j in 0..field.height {
+        for i in 0..field.width {
+            let Some(v) = field.get(i, j).filter(|v| v.is_finite()) else { continue };
+            let center = field.cell_center(i, j);
+            let (x, y) = to_screen(center.x, center.y);
+            let (r, g, b) = colormap((v - min) as f64 / span);
+            cr.set_source_rgba(r, g, b, opacity);
+            // Half a pixel of overlap hides seams between cells
+            cr.rectangle(x - size / 2.0, y - size / 2.0, size + 0.5, size + 0.5);
+            cr.fill()?;
+        }
+    }
+    Ok(())
+}
+
+/// Vertical colour bar with the field name and range, top at (x, y)
+pub fn draw_legend(cr: &cairo::Context, x: f64, y: f64, name: &str, (min, max): (f32, f32)) -> Result<(), cairo::Error> {
+    let (width, height, steps) = (12.0, 100.0, 50);
+    for s in 0..steps {
+        let (r, g, b) = colormap(1.0 - s as f64 / (steps - 1) as f64);
+        cr.set_source_rgb(r, g, b);
+        cr.rectangle(x, y + height * s as f64 / steps as f64, width, height / steps as f64 + 0.5);
+        cr.fill()?;
+    }
+    cr.set_source_rgb(0.0, 0.0, 0.0);
+    cr.set_line_width(1.0);
+    cr.rectangle(x, y, width, height);
+    cr.stroke()?;
+    cr.set_font_size(10.0);
+    for (text, ty) in [(name.to_string(), y - 4.0), (format!("{:.3}", max), y + 8.0), (format!("{:.3}", min), y + height)] {
+        cr.move_to(x + width + 4.0, ty);
+        cr.show_text(&text)?;
+    }
+    Ok(())
+}
+
+/// Everything one frame shows
+#[derive(Clone, Copy)]
+pub struct Scene<'a> {
+    pub plants: &'a [Plant],
+    pub environment: &'a Environment,
+    pub snapshot: Option<&'a CSpaceSnapshot>, // Heatmap layers are skipped without one
+}
+
+impl<'a> Scene<'a> {
+    pub fn of(env: &'a SimulationEnv, snapshot: Option<&'a CSpaceSnapshot>) -> Self {
+        Scene { plants: &env.plants, environment: &env.environment, snapshot }
+    }
+}
+
+/// Draws `scene` centred on the origin into a `width` × `height` area: the enabled heatmap
+/// layers with one legend each down the right edge, then the plants, then the light and water
+pub fn draw_scene(
+    cr: &cairo::Context,
+    width: f64,
+    height: f64,
+    scene: &Scene,
+    overlay: &mut OverlaySettings,
+) -> Result<(), cairo::Error> {
+    let center_x = width / 2.0;
+    let center_y = height / 2.0;
+    let to_screen = |x: f32, y: f32| (center_x + x as f64 * SCALE, center_y - y as f64 * SCALE);
+
+    if let Some(snapshot) = scene.snapshot {
+        let opacity = overlay.opacity;
+        let mut legend_y = 20.0;
+        for layer in overlay.layers.iter_mut().filter(|l| l.enabled) {
+            let Some(field) = snapshot.field(layer.kind) else { continue };
+            let range = layer.range(field);
+            draw_field(cr, field, range, opacity, SCALE, to_screen)?;
+            draw_legend(cr, width - 70.0, legend_y, layer.kind.name(), range)?;
+            legend_y += 125.0;
+        }
+    }
+
+    for plant in scene.plants {
+        let (pos_x, pos_y) = to_screen(plant.pos.x, plant.pos.y);
+        let (stem_x, stem_y) = to_screen(plant.pos.x + plant.stem_dir.x, plant.pos.y + plant.stem_dir.y);
+        let (root_x, root_y) = to_screen(plant.pos.x + plant.root_dir.x, plant.pos.y + plant.root_dir.y);
+
+        cr.set_source_rgb(0.0, 1.0, 0.0); // Green for stem
+        cr.move_to(pos_x, pos_y);
+        cr.line_to(stem_x, stem_y);
+        cr.stroke()?;
+
+        cr.set_source_rgb(0.65, 0.16, 0.16); // Brown for roots
+        cr.move_to(pos_x, pos_y);
+        cr.line_to(root_x, root_y);
+        cr.stroke()?;
+
+        cr.set_source_rgb(1.0, 0.0, 0.0); // Red dot for plant base
+        cr.arc(pos_x, pos_y, 3.0, 0.0, 2.0 * std::f64::consts::PI);
+        cr.fill()?;
+    }
+
+    let (light_x, light_y) = to_screen(scene.environment.light_pos.x, scene.environment.light_pos.y);
+    cr.set_source_rgb(1.0, 1.0, 0.0); // Yellow for light
+    cr.arc(light_x, light_y, 5.0, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill()?;
+
+    let (water_x, water_y) = to_screen(scene.environment.water_pos.x, scene.environment.water_pos.y);
+    cr.set_source_rgb(0.0, 0.0, 1.0); // Blue for water
+    cr.arc(water_x, water_y, 5.0, 0.0, 2.0 * std::f64::consts::PI);
+    cr.fill()
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    Cairo(cairo::Error),
+    Io(io::Error),
+    Apng(ApngError),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Cairo(e) => write!(f, "cairo: {}", e),
+            RenderError::Io(e) => e.fmt(f),
+            RenderError::Apng(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<cairo::Error> for RenderError {
+    fn from(e: cairo::Error) -> Self {
+        RenderError::Cairo(e)
+    }
+}
+
+impl From<io::Error> for RenderError {
+    fn from(e: io::Error) -> Self {
+        RenderError::Io(e)
+    }
+}
+
+impl From<ApngError> for RenderError {
+    fn from(e: ApngError) -> Self {
+        RenderError::Apng(e)
+    }
+}
+
+impl From<cairo::IoError> for RenderError {
+    fn from(e: cairo::IoError) -> Self {
+        match e {
+            cairo::IoError::Cairo(e) => RenderError::Cairo(e),
+            cairo::IoError::Io(e) => RenderError::Io(e),
+        }
+    }
+}
+
+/// Draws scenes into an image instead of a window, with the same layout as the Simulation View
+#[derive(Debug, Clone)]
+pub struct FrameRenderer {
+    pub width: i32,
+    pub height: i32,
+    pub background: (f64, f64, f64),
+    pub overlay: OverlaySettings,
+}
+
+impl FrameRenderer {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self { width, height, background: (1.0, 1.0, 1.0), overlay: OverlaySettings::default() }
+    }
+
+    pub fn render(&mut self, scene: &Scene) -> Result<cairo::ImageSurface, RenderError> {
+        let surface = cairo::ImageSurface::create(cairo::Format::Rgb24, self.width, self.height)?;
+        let cr = cairo::Context::new(&surface)?;
+        let (r, g, b) = self.background;
+        cr.set_source_rgb(r, g, b);
+        cr.paint()?;
+        draw_scene(&cr, self.width as f64, self.height as f64, scene, &mut self.overlay)?;
+        drop(cr);
+        Ok(surface)
+    }
+
+    /// The frame encoded as PNG
+    pub fn render_png(&mut self, scene: &Scene) -> Result<Vec<u8>, RenderError> {
+        let mut png = Vec::new();
+        self.render(scene)?.write_to_png(&mut png)?;
+        Ok(png)
+    }
+}
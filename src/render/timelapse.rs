@@ -0,0 +1,97 @@
+// src/render/timelapse.rs
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use crate::simulation::simulation_env::SimulationEnv;
+use crate::simulation::simulation_runner::{SimulationEvent, SimulationObserver};
+use super::apng::ApngWriter;
+use super::scene::{FrameRenderer, RenderError, Scene};
+
+#[derive(Debug, Clone)]
+pub struct TimeLapseConfig {
+    pub every: u64,                  // Render steps that are a multiple of this
+    pub width: i32,
+    pub height: i32,
+    pub frames_dir: Option<PathBuf>, // Write each frame here as frame_<step>.png
+    pub animation: Option<PathBuf>,  // Join the frames into this animated PNG at the end
+    pub fps: u16,
+}
+
+impl Default for TimeLapseConfig {
+    fn default() -> Self {
+        Self { every: 10, width: 400, height: 400, frames_dir: None, animation: None, fps: 10 }
+    }
+}
+
+/// Observer that renders the world offscreen every `every` steps, saving PNG frames and/or
+/// an animated PNG. Animation frames are streamed to the file as they are rendered, so memory
+/// use does not grow with the run; the file is completed by `finish`, or when the time-lapse
+/// is dropped.
+pub struct TimeLapse {
+    config: TimeLapseConfig,
+    renderer: FrameRenderer,
+    animation: Option<ApngWriter<BufWriter<File>>>, // None if not asked for, or once finished
+    frames: usize,                                  // Frames added to the animation
+    last_step: Option<u64>,
+}
+
+impl TimeLapse {
+    pub fn new(config: TimeLapseConfig) -> Result<Self, RenderError> {
+        if let Some(dir) = &config.frames_dir {
+            fs::create_dir_all(dir)?;
+        }
+        let animation = match &config.animation {
+            Some(path) => Some(ApngWriter::new(BufWriter::new(File::create(path)?), config.fps)),
+            None => None,
+        };
+        let renderer = FrameRenderer::new(config.width, config.height);
+        Ok(Self { config, renderer, animation, frames: 0, last_step: None })
+    }
+
+    /// Renders the current world, unless this step was already rendered
+    pub fn capture(&mut self, scene: &Scene, step: u64) -> Result<(), RenderError> {
+        if self.last_step == Some(step) {
+            return Ok(());
+        }
+        self.last_step = Some(step);
+        let png = self.renderer.render_png(scene)?;
+        if let Some(dir) = &self.config.frames_dir {
+            fs::write(dir.join(format!("frame_{:06}.png", step)), &png)?;
+        }
+        if let Some(animation) = &mut self.animation {
+            animation.add_frame(&png)?;
+            self.frames = animation.frames();
+        }
+        Ok(())
+    }
+
+    /// Completes the animation, if one was asked for; returns the number of frames in it
+    pub fn finish(&mut self) -> Result<usize, RenderError> {
+        if let Some(animation) = self.animation.take() {
+            animation.finish()?;
+        }
+        Ok(self.frames)
+    }
+}
+
+impl Drop for TimeLapse {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            eprintln!("Time-lapse failed: {}", e);
+        }
+    }
+}
+
+impl SimulationObserver for TimeLapse {
+    fn notify(&mut self, env: &SimulationEnv, event: &SimulationEvent) -> bool {
+        if let SimulationEvent::Snapshot(snapshot) = event {
+            if self.config.every > 0 && env.step.is_multiple_of(self.config.every) {
+                if let Err(e) = self.capture(&Scene::of(env, Some(snapshot)), env.step) {
+                    eprintln!("Time-lapse stopped: {}", e);
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
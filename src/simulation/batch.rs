@@ -0,0 +1,351 @@
+// src/simulation/batch.rs
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::engines::plant_engine::PlantEngine;
+use super::headless::{run_headless, RunMetrics};
+use super::parameters::ParameterError;
+use super::simulation_env::SimulationEnv;
+
+/// One swept parameter: `points` values evenly spaced over [min, max] in grid mode,
+/// uniform samples from [min, max] in random mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepAxis {
+    pub name: String,
+    pub min: f32,
+    pub max: f32,
+    pub points: usize,
+}
+
+impl SweepAxis {
+    /// Parses `name=min:max` or `name=min:max:points`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (name, range) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("expected name=min:max[:points], got '{}'", spec))?;
+        let parts: Vec<&str> = range.split(':').collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return Err(format!("expected min:max[:points] for '{}', got '{}'", name, range));
+        }
+        let number = |s: &str| s.trim().parse::<f32>().map_err(|_| format!("invalid number '{}' in '{}'", s, spec));
+        let points = match parts.get(2) {
+            Some(p) => p.trim().parse().map_err(|_| format!("invalid point count '{}' in '{}'", p, spec))?,
+            None => 5,
+        };
+        Ok(Self {
+            name: name.trim().to_string(),
+            min: number(parts[0])?,
+            max: number(parts[1])?,
+            points,
+        })
+    }
+
+    /// Evenly spaced values from min to max; just `min` when `points` is 0 or 1
+    pub fn values(&self) -> Vec<f32> {
+        if self.points <= 1 {
+            return vec![self.min];
+        }
+        (0..self.points)
+            .map(|i| self.min + (self.max - self.min) * i as f32 / (self.points - 1) as f32)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepMode {
+    /// Full factorial grid over every axis
+    Grid,
+    /// `samples` uniformly random configurations
+    Random { samples: usize },
+}
+
+/// What to sweep and how to run each configuration
+#[derive(Debug, Clone)]
+pub struct SweepSpec {
+    pub axes: Vec<SweepAxis>,
+    pub mode: SweepMode,
+    pub steps: usize,
+    pub dt: f32,
+    pub workers: usize,
+    pub seed: u64, // Seeds random sampling and every simulation
+}
+
+impl Default for SweepSpec {
+    fn default() -> Self {
+        Self {
+            axes: Vec::new(),
+            mode: SweepMode::Grid,
+            steps: 500,
+            dt: 0.1,
+            workers: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            seed: 42,
+        }
+    }
+}
+
+impl SweepSpec {
+    /// Checks that every axis can be set to its min, its max and every value the sweep samples
+    pub fn validate(&self, template: &SimulationEnv) -> Result<(), ParameterError> {
+        let names: Vec<&str> = self.axes.iter().map(|a| a.name.as_str()).collect();
+        let bounds = [
+            self.axes.iter().map(|a| a.min).collect(),
+            self.axes.iter().map(|a| a.max).collect(),
+        ];
+        check_configurations(template, &names, &bounds)?;
+        check_configurations(template, &names, &self.configurations())
+    }
+
+    /// Parameter values for every configuration, in axis order
+    pub fn configurations(&self) -> Vec<Vec<f32>> {
+        match self.mode {
+            SweepMode::Grid => {
+                let mut configs = vec![Vec::new()];
+                for axis in &self.axes {
+                    configs = configs
+                        .into_iter()
+                        .flat_map(|prefix| {
+                            axis.values().into_iter().map(move |value| {
+                                let mut config = prefix.clone();
+                                config.push(value);
+                                config
+                            })
+                        })
+                        .collect();
+                }
+                configs
+            }
+            SweepMode::Random { samples } => {
+                let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+                (0..samples)
+                    .map(|_| {
+                        self.axes
+                            .iter()
+                            .map(|a| if a.max > a.min { rng.gen_range(a.min..a.max) } else { a.min })
+                            .collect()
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Result of one configuration
+#[derive(Debug, Clone)]
+pub struct SweepRow {
+    pub index: usize,
+    pub parameters: Vec<f32>,
+    pub metrics: RunMetrics,
+}
+
+/// Mean, spread and range of one metric across all configurations
+#[derive(Debug, Clone)]
+pub struct MetricSummary {
+    pub metric: &'static str,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl fmt::Display for MetricSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<18} mean={:>10.4} std={:>10.4} min={:>10.4} max={:>10.4}",
+            self.metric, self.mean, self.std_dev, self.min, self.max
+        )
+    }
+}
+
+/// Runs every configuration of `spec` on a pool of worker threads, starting each from a copy of `template`
+pub fn run_sweep(spec: &SweepSpec, template: &SimulationEnv) -> Result<Vec<SweepRow>, ParameterError> {
+    spec.validate(template)?;
+    let names: Vec<&str> = spec.axes.iter().map(|a| a.name.as_str()).collect();
+    run_checked(template, &names, &spec.configurations(), spec.steps, spec.dt, spec.seed, spec.workers)
+}
+
+/// Runs one headless simulation per entry of `configs` (values for `names`, in order) on `workers` threads.
+/// Every run is reseeded with `seed`, so differences come from the parameters alone.
+pub fn run_configurations(
+    template: &SimulationEnv,
+    names: &[&str],
+    configs: &[Vec<f32>],
+    steps: usize,
+    dt: f32,
+    seed: u64,
+    workers: usize,
+) -> Result<Vec<SweepRow>, ParameterError> {
+    // Validate every value up front so a typo or an out-of-range value fails before any work is done
+    check_configurations(template, names, configs)?;
+    run_checked(template, names, configs, steps, dt, seed, workers)
+}
+
+/// Sets every value of `configs` on a scratch copy of `template`, stopping at the first it rejects
+fn check_configurations(template: &SimulationEnv, names: &[&str], configs: &[Vec<f32>]) -> Result<(), ParameterError> {
+    let mut probe = template.clone();
+    for parameters in configs {
+        for (name, value) in names.iter().zip(parameters) {
+            probe.set_parameter(name, *value)?;
+        }
+    }
+    Ok(())
+}
+
+/// `run_configurations` for configurations already checked with `check_configurations`
+fn run_checked(
+    template: &SimulationEnv,
+    names: &[&str],
+    configs: &[Vec<f32>],
+    steps: usize,
+    dt: f32,
+    seed: u64,
+    workers: usize,
+) -> Result<Vec<SweepRow>, ParameterError> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = channel();
+    std::thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(parameters) = configs.get(index) else { break };
+                let row = run_configuration(template, names, parameters, steps, dt, seed)
+                    .map(|metrics| SweepRow { index, parameters: parameters.clone(), metrics });
+                let failed = row.is_err();
+                if sender.send(row).is_err() || failed {
+                    break;
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut rows = receiver.into_iter().collect::<Result<Vec<SweepRow>, _>>()?;
+    rows.sort_by_key(|r| r.index);
+    Ok(rows)
+}
+
+/// One run from a reseeded copy of `template` with `parameters` applied to `names`
+fn run_configuration(
+    template: &SimulationEnv,
+    names: &[&str],
+    parameters: &[f32],
+    steps: usize,
+    dt: f32,
+    seed: u64,
+) -> Result<RunMetrics, ParameterError> {
+    let mut env = template.clone();
+    env.reseed(seed);
+    for (name, value) in names.iter().zip(parameters) {
+        env.set_parameter(name, *value)?;
+    }
+    let mut engine = PlantEngine::new(env);
+    Ok(run_headless(&mut engine, steps, dt))
+}
+
+/// Summary statistics for every metric over `rows`
+pub fn summarize(rows: &[SweepRow]) -> Vec<MetricSummary> {
+    RunMetrics::METRICS
+        .iter()
+        .map(|&metric| {
+            let values: Vec<f32> = rows.iter().filter_map(|r| r.metrics.get(metric)).collect();
+            let n = values.len().max(1) as f32;
+            let mean = values.iter().sum::<f32>() / n;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / n;
+            MetricSummary {
+                metric,
+                mean,
+                std_dev: variance.sqrt(),
+                min: values.iter().copied().fold(f32::INFINITY, f32::min),
+                max: values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            }
+        })
+        .collect()
+}
+
+/// Writes one CSV row per configuration: index, swept parameters, then every metric
+pub fn write_results_csv(path: &Path, spec: &SweepSpec, rows: &[SweepRow]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(out, "index")?;
+    for axis in &spec.axes {
+        write!(out, ",{}", axis.name)?;
+    }
+    for metric in RunMetrics::METRICS {
+        write!(out, ",{}", metric)?;
+    }
+    writeln!(out)?;
+    for row in rows {
+        write!(out, "{}", row.index)?;
+        for value in &row.parameters {
+            write!(out, ",{}", value)?;
+        }
+        for metric in RunMetrics::METRICS {
+            write!(out, ",{}", row.metrics.get(metric).unwrap_or(f32::NAN))?;
+        }
+        writeln!(out)?;
+    }
+    out.flush()
+}
+
+/// Writes the per-metric summary as CSV
+pub fn write_summary_csv(path: &Path, summary: &[MetricSummary]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "metric,mean,std_dev,min,max")?;
+    for s in summary {
+        writeln!(out, "{},{},{},{},{}", s.metric, s.mean, s.std_dev, s.min, s.max)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_configurations() {
+        let spec = SweepSpec {
+            axes: vec![
+                SweepAxis::parse("light_intensity=0.5:1.5:3").unwrap(),
+                SweepAxis::parse("beta=0.1:0.2:2").unwrap(),
+            ],
+            ..SweepSpec::default()
+        };
+        let configs = spec.configurations();
+        assert_eq!(configs.len(), 6);
+        assert_eq!(configs[0], vec![0.5, 0.1]);
+        assert_eq!(configs[5], vec![1.5, 0.2]);
+    }
+
+    #[test]
+    fn test_run_sweep() {
+        let spec = SweepSpec {
+            axes: vec![SweepAxis::parse("d_critical=1:20:2").unwrap()],
+            mode: SweepMode::Grid,
+            steps: 20,
+            workers: 2,
+            ..SweepSpec::default()
+        };
+        let rows = run_sweep(&spec, &SimulationEnv::with_seed(1)).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].parameters, vec![20.0]);
+
+        let bad = SweepSpec { axes: vec![SweepAxis::parse("gamma=0:1").unwrap()], ..spec.clone() };
+        assert!(run_sweep(&bad, &SimulationEnv::with_seed(1)).is_err());
+        // The min is fine but later values are negative
+        let out_of_range = SweepSpec { axes: vec![SweepAxis::parse("hydrotropism=0:-1").unwrap()], ..spec };
+        assert!(matches!(
+            run_sweep(&out_of_range, &SimulationEnv::with_seed(1)),
+            Err(ParameterError::Invalid { .. })
+        ));
+        // Direct callers get the same check for every configuration, not just the first
+        let configs = [vec![0.1], vec![-1.0]];
+        let result = run_configurations(&SimulationEnv::with_seed(1), &["hydrotropism"], &configs, 5, 0.1, 1, 2);
+        assert!(matches!(result, Err(ParameterError::Invalid { .. })));
+    }
+}
@@ -0,0 +1,117 @@
+// src/simulation/checkpoint.rs
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use super::simulation_env::SimulationEnv;
+
+/// Format version written after the magic bytes; bump it whenever `SimulationEnv` changes shape
+pub const CHECKPOINT_VERSION: u32 = 1;
+/// First bytes of every checkpoint file
+const MAGIC: &[u8; 8] = b"CSPCHKPT";
+
+/// A saved world plus the runner's timestep, enough to continue a run bit-for-bit.
+/// Stored as `MAGIC`, the version (little-endian u32), then the bincode-encoded checkpoint,
+/// which keeps every f32 and the RNG's stream position exact.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub env: SimulationEnv,
+    pub dt: f32,
+}
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    NotACheckpoint,
+    UnsupportedVersion(u32),
+    Corrupt(String),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(e) => e.fmt(f),
+            CheckpointError::NotACheckpoint => write!(f, "not a checkpoint file"),
+            CheckpointError::UnsupportedVersion(v) => {
+                write!(f, "checkpoint version {} is not supported (expected {})", v, CHECKPOINT_VERSION)
+            }
+            CheckpointError::Corrupt(reason) => write!(f, "corrupt checkpoint: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<io::Error> for CheckpointError {
+    fn from(e: io::Error) -> Self {
+        CheckpointError::Io(e)
+    }
+}
+
+impl Checkpoint {
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), CheckpointError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&CHECKPOINT_VERSION.to_le_bytes())?;
+        bincode::serialize_into(&mut writer, self).map_err(|e| CheckpointError::Corrupt(e.to_string()))?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, CheckpointError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(|_| CheckpointError::NotACheckpoint)?;
+        if &magic != MAGIC {
+            return Err(CheckpointError::NotACheckpoint);
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(version));
+        }
+        bincode::deserialize_from(reader).map_err(|e| CheckpointError::Corrupt(e.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CheckpointError> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::plant_engine::PlantEngine;
+
+    #[test]
+    fn test_checkpoint_continues_exactly() {
+        let mut engine = PlantEngine::new(SimulationEnv::with_seed(11));
+        for _ in 0..20 {
+            engine.update(0.1);
+        }
+        let mut bytes = Vec::new();
+        Checkpoint { env: engine.env.clone(), dt: 0.1 }.write(&mut bytes).unwrap();
+        let restored = Checkpoint::read(bytes.as_slice()).unwrap();
+        assert_eq!(restored.dt, 0.1);
+
+        // The original and the restored copy stay bit-identical, including RNG draws
+        let mut resumed = PlantEngine::new(restored.env);
+        for _ in 0..30 {
+            engine.update(0.1);
+            resumed.update(0.1);
+        }
+        assert_eq!(engine.env.step, resumed.env.step);
+        assert_eq!(engine.env.plants.len(), resumed.env.plants.len());
+        for (a, b) in engine.env.plants.iter().zip(&resumed.env.plants) {
+            assert_eq!((a.id, a.pos, a.energy, a.coherence, a.distortion), (b.id, b.pos, b.energy, b.coherence, b.distortion));
+        }
+
+        bytes[8] = 99;
+        assert!(matches!(Checkpoint::read(bytes.as_slice()), Err(CheckpointError::UnsupportedVersion(99))));
+        assert!(matches!(Checkpoint::read(&b"not a checkpoint"[..]), Err(CheckpointError::NotACheckpoint)));
+    }
+}
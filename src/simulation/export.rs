@@ -0,0 +1,282 @@
+// src/simulation/export.rs
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use nalgebra::Vector3;
+use crate::plants::tropisms::{Plant, TropismKind, TropismResult, Tropisms};
+use super::simulation_env::SimulationEnv;
+use super::simulation_runner::{SimulationEvent, SimulationObserver};
+
+/// Columns of every exported row, in order; `step` and `plant_id` are integers, the rest f32
+pub const COLUMNS: [&str; 33] = [
+    "step", "time", "plant_id",
+    "pos_x", "pos_y", "pos_z",
+    "stem_x", "stem_y", "stem_z",
+    "root_x", "root_y", "root_z",
+    "energy", "coherence", "distortion", "temporal_complexity", "spatial_complexity", "rho_c",
+    "phototropism_dx", "phototropism_dy", "phototropism_dz",
+    "gravitropism_dx", "gravitropism_dy", "gravitropism_dz",
+    "hydrotropism_dx", "hydrotropism_dy", "hydrotropism_dz",
+    "thigmotropism_dx", "thigmotropism_dy", "thigmotropism_dz",
+    "chemotropism_dx", "chemotropism_dy", "chemotropism_dz",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv, // One header line, then one line per row
+    Npy, // A NumPy structured array with one named field per column
+}
+
+impl ExportFormat {
+    /// Picks the format from the file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(ExportFormat::Csv),
+            "npy" => Some(ExportFormat::Npy),
+            _ => None,
+        }
+    }
+}
+
+/// One plant after one step
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlantRow {
+    pub step: u64,
+    pub time: f32,
+    pub plant_id: usize,
+    pub pos: Vector3<f32>,
+    pub stem_dir: Vector3<f32>,
+    pub root_dir: Vector3<f32>,
+    pub energy: f32,
+    pub coherence: f32,
+    pub distortion: f32,
+    pub temporal_complexity: f32,
+    pub spatial_complexity: f32,
+    pub rho_c: f32,
+    pub growth: [Vector3<f32>; 5], // Growth delta per tropism, in `TropismKind::ALL` order
+}
+
+impl PlantRow {
+    /// Rows for every plant in `env`, with growth deltas from the step's tropism `results`
+    pub fn rows(env: &SimulationEnv, results: &[Vec<TropismResult>]) -> Vec<PlantRow> {
+        env.plants
+            .iter()
+            .map(|plant| {
+                let mut growth = [Vector3::zeros(); 5];
+                for result in results.iter().flatten().filter(|r| r.plant_id == plant.id) {
+                    if let Some(i) = TropismKind::ALL.iter().position(|k| *k == result.kind) {
+                        growth[i] += result.growth_delta;
+                    }
+                }
+                Self::of(env, plant, growth)
+            })
+            .collect()
+    }
+
+    fn of(env: &SimulationEnv, plant: &Plant, growth: [Vector3<f32>; 5]) -> Self {
+        PlantRow {
+            step: env.step,
+            time: env.time,
+            plant_id: plant.id,
+            pos: plant.pos,
+            stem_dir: plant.stem_dir,
+            root_dir: plant.root_dir,
+            energy: plant.energy,
+            coherence: plant.coherence,
+            distortion: plant.distortion,
+            temporal_complexity: plant.temporal_complexity,
+            spatial_complexity: plant.spatial_complexity,
+            rho_c: Tropisms::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy),
+            growth,
+        }
+    }
+
+    /// Every f32 column after `step`, `time` and `plant_id`
+    fn values(&self) -> Vec<f32> {
+        let mut values = Vec::with_capacity(COLUMNS.len() - 2);
+        values.push(self.time);
+        for v in [self.pos, self.stem_dir, self.root_dir] {
+            values.extend(v.iter());
+        }
+        values.extend([
+            self.energy,
+            self.coherence,
+            self.distortion,
+            self.temporal_complexity,
+            self.spatial_complexity,
+            self.rho_c,
+        ]);
+        for g in &self.growth {
+            values.extend(g.iter());
+        }
+        values
+    }
+}
+
+enum Sink {
+    Csv(BufWriter<File>),
+    Npy { out: BufWriter<File>, rows: u64, header_len: usize },
+}
+
+/// Streams `PlantRow`s to a CSV or NPY file. It is also an observer, so it can be attached
+/// to a runner and export every step as it happens; the file is finalized when it is dropped,
+/// which `ControlCommand::Detach` does at once.
+pub struct Exporter {
+    sink: Sink,
+    finished: bool,
+}
+
+impl Exporter {
+    pub fn create(path: &Path, format: ExportFormat) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        let sink = match format {
+            ExportFormat::Csv => {
+                writeln!(out, "{}", COLUMNS.join(","))?;
+                Sink::Csv(out)
+            }
+            ExportFormat::Npy => {
+                // Reserve room for any row count; `finish` rewrites the header in place
+                let header = npy_header(u64::MAX, None);
+                out.write_all(&header)?;
+                Sink::Npy { out, rows: 0, header_len: header.len() }
+            }
+        };
+        Ok(Self { sink, finished: false })
+    }
+
+    pub fn write(&mut self, rows: &[PlantRow]) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Csv(out) => {
+                for row in rows {
+                    write!(out, "{},", row.step)?;
+                    let values = row.values();
+                    write!(out, "{},{}", values[0], row.plant_id)?;
+                    for v in &values[1..] {
+                        write!(out, ",{}", v)?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+            Sink::Npy { out, rows: count, .. } => {
+                for row in rows {
+                    out.write_all(&row.step.to_le_bytes())?;
+                    let values = row.values();
+                    out.write_all(&values[0].to_le_bytes())?;
+                    out.write_all(&(row.plant_id as u64).to_le_bytes())?;
+                    for v in &values[1..] {
+                        out.write_all(&v.to_le_bytes())?;
+                    }
+                    *count += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes and, for NPY, writes the final row count into the header
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        match &mut self.sink {
+            Sink::Csv(out) => out.flush(),
+            Sink::Npy { out, rows, header_len } => {
+                out.seek(SeekFrom::Start(0))?;
+                out.write_all(&npy_header(*rows, Some(*header_len)))?;
+                out.seek(SeekFrom::End(0))?;
+                out.flush()
+            }
+        }
+    }
+}
+
+impl Drop for Exporter {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            eprintln!("Export failed: {}", e);
+        }
+    }
+}
+
+impl SimulationObserver for Exporter {
+    fn notify(&mut self, env: &SimulationEnv, event: &SimulationEvent) -> bool {
+        if let SimulationEvent::Step(results) = event {
+            if let Err(e) = self.write(&PlantRow::rows(env, results)) {
+                eprintln!("Export stopped: {}", e);
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// NPY 1.0 header for `rows` records of `COLUMNS`, padded with spaces to `len` bytes
+/// (or to the next multiple of 64)
+fn npy_header(rows: u64, len: Option<usize>) -> Vec<u8> {
+    let fields: Vec<String> = COLUMNS
+        .iter()
+        .map(|&c| {
+            let dtype = if c == "step" || c == "plant_id" { "<u8" } else { "<f4" };
+            format!("('{}', '{}')", c, dtype)
+        })
+        .collect();
+    let mut dict = format!("{{'descr': [{}], 'fortran_order': False, 'shape': ({},), }}", fields.join(", "), rows);
+    // Magic (6) + version (2) + header length (2) + dict + '\n'
+    let total = len.unwrap_or_else(|| (10 + dict.len() + 1).div_ceil(64) * 64);
+    while 10 + dict.len() + 1 < total {
+        dict.push(' ');
+    }
+    dict.push('\n');
+    let mut header = b"\x93NUMPY\x01\x00".to_vec();
+    header.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    header.extend_from_slice(dict.as_bytes());
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::simulation_runner::{ControlCommand, ObserverId, SimulationRunner};
+
+    #[test]
+    fn test_export_csv_and_npy() {
+        let dir = std::env::temp_dir();
+        let csv = dir.join(format!("cs_export_{}.csv", std::process::id()));
+        let npy = dir.join(format!("cs_export_{}.npy", std::process::id()));
+        let (mut runner, _commands) = SimulationRunner::new(SimulationEnv::with_seed(4));
+        runner.subscribe(Box::new(Exporter::create(&csv, ExportFormat::from_path(&csv).unwrap()).unwrap()));
+        let id = ObserverId::fresh();
+        let exporter = Exporter::create(&npy, ExportFormat::from_path(&npy).unwrap()).unwrap();
+        runner.handle(ControlCommand::Attach(id, Box::new(exporter)));
+        for _ in 0..5 {
+            runner.step(0.1);
+        }
+        let plant = runner.env().plants[0].clone();
+        // Detaching finalizes the NPY file at once; the CSV export carries on
+        runner.handle(ControlCommand::Detach(id));
+        runner.step(0.1);
+        let later = runner.env().plants[0].clone();
+        drop(runner);
+
+        let text = std::fs::read_to_string(&csv).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0].split(',').count(), COLUMNS.len());
+        assert_eq!(lines.len(), 7);
+        let last: Vec<&str> = lines[6].split(',').collect();
+        assert_eq!(last[0], "6");
+        assert_eq!(last[12].parse::<f32>().unwrap(), later.energy);
+
+        let bytes = std::fs::read(&npy).unwrap();
+        let header_len = 10 + u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert!(header_len.is_multiple_of(64));
+        assert!(String::from_utf8_lossy(&bytes[..header_len]).contains("'shape': (5,)"));
+        let record = 2 * 8 + (COLUMNS.len() - 2) * 4;
+        assert_eq!(bytes.len(), header_len + 5 * record);
+        let energy_at = header_len + 4 * record + 8 + 4 + 8 + 9 * 4;
+        assert_eq!(f32::from_le_bytes(bytes[energy_at..energy_at + 4].try_into().unwrap()), plant.energy);
+
+        std::fs::remove_file(&csv).unwrap();
+        std::fs::remove_file(&npy).unwrap();
+    }
+}
@@ -0,0 +1,353 @@
+// src/simulation/geometry.rs
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+use nalgebra::Vector3;
+use crate::plants::tropisms::{Environment, Plant, Tropisms};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryFormat {
+    Obj, // Wavefront OBJ, one object per part; attributes become vertex colors
+    Ply, // ASCII PLY with attributes as extra vertex properties
+    Vtk, // Legacy VTK polydata with attributes as point data
+}
+
+impl GeometryFormat {
+    /// Picks the format from the file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "obj" => Some(GeometryFormat::Obj),
+            "ply" => Some(GeometryFormat::Ply),
+            "vtk" => Some(GeometryFormat::Vtk),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeometryOptions {
+    pub stem_radius: f32,
+    pub root_radius: f32,
+    pub sides: usize,        // Vertices around each tube ring
+    pub marker_size: f32,    // Half-width of obstacle, light and water markers
+    pub attributes: bool,    // Write ρ_c, coherence, distortion and plant id per vertex
+}
+
+impl Default for GeometryOptions {
+    fn default() -> Self {
+        Self { stem_radius: 0.05, root_radius: 0.03, sides: 8, marker_size: 0.2, attributes: false }
+    }
+}
+
+/// Values carried by every vertex; markers have no plant and zero values
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VertexValues {
+    pub plant: Option<usize>,
+    pub rho_c: f32,
+    pub coherence: f32,
+    pub distortion: f32,
+}
+
+/// A named group of faces, e.g. `plant_3_stem` or `light`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub name: String,
+    pub faces: Range<usize>,
+}
+
+/// Triangle mesh of the scene: plant tubes, obstacle cubes and light/water octahedra
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub vertices: Vec<Vector3<f32>>,
+    pub values: Vec<VertexValues>, // One per vertex
+    pub faces: Vec<[usize; 3]>,
+    pub parts: Vec<Part>,
+}
+
+impl Mesh {
+    /// Builds tubes along each plant's stem (its grown path, then `stem_dir` from the base)
+    /// and root (`root_dir` from the base), plus markers for obstacles and sources
+    pub fn build(plants: &[Plant], environment: &Environment, options: &GeometryOptions) -> Self {
+        let mut mesh = Mesh::default();
+        for plant in plants {
+            let values = VertexValues {
+                plant: Some(plant.id),
+                rho_c: Tropisms::compute_complex_density(plant.spatial_complexity, plant.temporal_complexity, plant.energy),
+                coherence: plant.coherence,
+                distortion: plant.distortion,
+            };
+            let mut stem = plant.path.clone();
+            stem.push(plant.pos);
+            stem.push(plant.pos + plant.stem_dir);
+            mesh.part(format!("plant_{}_stem", plant.id), |m| m.tube(&stem, options.stem_radius, options.sides, values));
+            let root = [plant.pos, plant.pos + plant.root_dir];
+            mesh.part(format!("plant_{}_root", plant.id), |m| m.tube(&root, options.root_radius, options.sides, values));
+        }
+        let size = options.marker_size;
+        for (i, obstacle) in environment.obstacles.iter().enumerate() {
+            mesh.part(format!("obstacle_{}", i), |m| m.cube(*obstacle, size));
+        }
+        mesh.part("light".to_string(), |m| m.octahedron(environment.light_pos, size));
+        mesh.part("water".to_string(), |m| m.octahedron(environment.water_pos, size));
+        mesh
+    }
+
+    fn part(&mut self, name: String, add: impl FnOnce(&mut Self)) {
+        let start = self.faces.len();
+        add(self);
+        if self.faces.len() > start {
+            self.parts.push(Part { name, faces: start..self.faces.len() });
+        }
+    }
+
+    fn vertex(&mut self, v: Vector3<f32>, values: VertexValues) -> usize {
+        self.vertices.push(v);
+        self.values.push(values);
+        self.vertices.len() - 1
+    }
+
+    /// Closed tube through `points`, with rings kept untwisted by carrying the normal along
+    fn tube(&mut self, points: &[Vector3<f32>], radius: f32, sides: usize, values: VertexValues) {
+        let mut points = points.to_vec();
+        points.dedup_by(|a, b| (*a - *b).norm() < 1e-6);
+        if points.len() < 2 || sides < 3 {
+            return;
+        }
+        // Where a point's neighbours coincide (the path doubles back) the previous direction is kept
+        let mut tangents = Vec::with_capacity(points.len());
+        for i in 0..points.len() {
+            let before = points[i.saturating_sub(1)];
+            let after = points[(i + 1).min(points.len() - 1)];
+            let previous = tangents.last().copied().unwrap_or_else(Vector3::x);
+            tangents.push((after - before).try_normalize(1e-6).unwrap_or(previous));
+        }
+        let first = tangents[0];
+        let helper = if first.x.abs() < 0.9 { Vector3::x() } else { Vector3::y() };
+        // Only parallel to `helper` if `first` is, and then z is square to both
+        let mut normal = first.cross(&helper).try_normalize(1e-6).unwrap_or_else(Vector3::z);
+
+        let base = self.vertices.len();
+        for (point, &t) in points.iter().zip(&tangents) {
+            normal = (normal - t * normal.dot(&t)).try_normalize(1e-6).unwrap_or(normal);
+            let binormal = t.cross(&normal);
+            for k in 0..sides {
+                let angle = k as f32 / sides as f32 * std::f32::consts::TAU;
+                self.vertex(point + (normal * angle.cos() + binormal * angle.sin()) * radius, values);
+            }
+        }
+        let ring = |i: usize, k: usize| base + i * sides + k % sides;
+        for i in 0..points.len() - 1 {
+            for k in 0..sides {
+                self.faces.push([ring(i, k), ring(i + 1, k + 1), ring(i + 1, k)]);
+                self.faces.push([ring(i, k), ring(i, k + 1), ring(i + 1, k + 1)]);
+            }
+        }
+        let last = points.len() - 1;
+        let start_cap = self.vertex(points[0], values);
+        let end_cap = self.vertex(points[last], values);
+        for k in 0..sides {
+            self.faces.push([start_cap, ring(0, k + 1), ring(0, k)]);
+            self.faces.push([end_cap, ring(last, k), ring(last, k + 1)]);
+        }
+    }
+
+    fn cube(&mut self, center: Vector3<f32>, size: f32) {
+        let base = self.vertices.len();
+        for i in 0..8 {
+            let corner = Vector3::new(
+                if i & 1 == 0 { -size } else { size },
+                if i & 2 == 0 { -size } else { size },
+                if i & 4 == 0 { -size } else { size },
+            );
+            self.vertex(center + corner, VertexValues::default());
+        }
+        const FACES: [[usize; 4]; 6] = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]];
+        for [a, b, c, d] in FACES {
+            self.faces.push([base + a, base + b, base + c]);
+            self.faces.push([base + a, base + c, base + d]);
+        }
+    }
+
+    fn octahedron(&mut self, center: Vector3<f32>, size: f32) {
+        let base = self.vertices.len();
+        for axis in [Vector3::x(), -Vector3::x(), Vector3::y(), -Vector3::y(), Vector3::z(), -Vector3::z()] {
+            self.vertex(center + axis * size, VertexValues::default());
+        }
+        for (x, y, z) in [(0, 2, 4), (2, 1, 4), (1, 3, 4), (3, 0, 4), (2, 0, 5), (1, 2, 5), (3, 1, 5), (0, 3, 5)] {
+            self.faces.push([base + x, base + y, base + z]);
+        }
+    }
+
+    pub fn save(&self, path: &Path, format: GeometryFormat, attributes: bool) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        match format {
+            GeometryFormat::Obj => self.write_obj(&mut out, attributes)?,
+            GeometryFormat::Ply => self.write_ply(&mut out, attributes)?,
+            GeometryFormat::Vtk => self.write_vtk(&mut out, attributes)?,
+        }
+        out.flush()
+    }
+
+    /// OBJ has no scalar fields, so attributes are written as `v x y z r g b` colors ramping
+    /// blue to red over the plants' ρ_c range; markers stay grey
+    pub fn write_obj<W: Write>(&self, out: &mut W, attributes: bool) -> io::Result<()> {
+        writeln!(out, "# C-Space Simulator geometry")?;
+        let (lo, hi) = self.rho_c_range();
+        for (v, values) in self.vertices.iter().zip(&self.values) {
+            write!(out, "v {} {} {}", v.x, v.y, v.z)?;
+            if attributes {
+                let (r, g, b) = match values.plant {
+                    Some(_) => {
+                        let t = if hi > lo { (values.rho_c - lo) / (hi - lo) } else { 0.5 };
+                        (t, 1.0 - (2.0 * t - 1.0).abs(), 1.0 - t)
+                    }
+                    None => (0.5, 0.5, 0.5),
+                };
+                write!(out, " {} {} {}", r, g, b)?;
+            }
+            writeln!(out)?;
+        }
+        for part in &self.parts {
+            writeln!(out, "o {}", part.name)?;
+            for [a, b, c] in &self.faces[part.faces.clone()] {
+                writeln!(out, "f {} {} {}", a + 1, b + 1, c + 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_ply<W: Write>(&self, out: &mut W, attributes: bool) -> io::Result<()> {
+        writeln!(out, "ply\nformat ascii 1.0\ncomment C-Space Simulator geometry")?;
+        writeln!(out, "element vertex {}", self.vertices.len())?;
+        writeln!(out, "property float x\nproperty float y\nproperty float z")?;
+        if attributes {
+            writeln!(out, "property float rho_c\nproperty float coherence\nproperty float distortion\nproperty int plant")?;
+        }
+        writeln!(out, "element face {}", self.faces.len())?;
+        writeln!(out, "property list uchar int vertex_indices\nend_header")?;
+        for (v, values) in self.vertices.iter().zip(&self.values) {
+            write!(out, "{} {} {}", v.x, v.y, v.z)?;
+            if attributes {
+                write!(out, " {} {} {} {}", values.rho_c, values.coherence, values.distortion, plant_label(values))?;
+            }
+            writeln!(out)?;
+        }
+        for [a, b, c] in &self.faces {
+            writeln!(out, "3 {} {} {}", a, b, c)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_vtk<W: Write>(&self, out: &mut W, attributes: bool) -> io::Result<()> {
+        writeln!(out, "# vtk DataFile Version 3.0\nC-Space Simulator geometry\nASCII\nDATASET POLYDATA")?;
+        writeln!(out, "POINTS {} float", self.vertices.len())?;
+        for v in &self.vertices {
+            writeln!(out, "{} {} {}", v.x, v.y, v.z)?;
+        }
+        writeln!(out, "POLYGONS {} {}", self.faces.len(), self.faces.len() * 4)?;
+        for [a, b, c] in &self.faces {
+            writeln!(out, "3 {} {} {}", a, b, c)?;
+        }
+        if attributes {
+            writeln!(out, "POINT_DATA {}", self.vertices.len())?;
+            for name in ["rho_c", "coherence", "distortion"] {
+                writeln!(out, "SCALARS {} float 1\nLOOKUP_TABLE default", name)?;
+                for values in &self.values {
+                    let value = match name {
+                        "rho_c" => values.rho_c,
+                        "coherence" => values.coherence,
+                        _ => values.distortion,
+                    };
+                    writeln!(out, "{}", value)?;
+                }
+            }
+            writeln!(out, "SCALARS plant int 1\nLOOKUP_TABLE default")?;
+            for values in &self.values {
+                writeln!(out, "{}", plant_label(values))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn rho_c_range(&self) -> (f32, f32) {
+        self.values
+            .iter()
+            .filter(|v| v.plant.is_some() && v.rho_c.is_finite())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| (lo.min(v.rho_c), hi.max(v.rho_c)))
+    }
+}
+
+/// Plant id as written to files, -1 for markers
+fn plant_label(values: &VertexValues) -> i64 {
+    values.plant.map_or(-1, |id| id as i64)
+}
+
+/// Builds the mesh of `plants` in `environment` and writes it in the format the extension names
+pub fn export_geometry(path: &Path, plants: &[Plant], environment: &Environment, options: &GeometryOptions) -> io::Result<()> {
+    let format = GeometryFormat::from_path(path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "use a .obj, .ply or .vtk file")
+    })?;
+    Mesh::build(plants, environment, options).save(path, format, options.attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::plant_engine::PlantEngine;
+    use crate::simulation::simulation_env::SimulationEnv;
+
+    #[test]
+    fn test_mesh_is_closed_and_written() {
+        let mut engine = PlantEngine::new(SimulationEnv::with_seed(6));
+        for _ in 0..40 {
+            engine.update(0.1);
+        }
+        let env = &engine.env;
+        let options = GeometryOptions { attributes: true, ..GeometryOptions::default() };
+        let mesh = Mesh::build(&env.plants, &env.environment, &options);
+        assert_eq!(mesh.parts.len(), 2 * env.plants.len() + env.environment.obstacles.len() + 2);
+        assert_eq!(mesh.values.len(), mesh.vertices.len());
+
+        // Every edge of a closed surface is shared by exactly two faces, in opposite directions
+        let mut edges = std::collections::HashMap::new();
+        for [a, b, c] in &mesh.faces {
+            for edge in [(*a, *b), (*b, *c), (*c, *a)] {
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+        assert!(edges.iter().all(|(&(a, b), &n)| n == 1 && edges.get(&(b, a)) == Some(&1)));
+        // ...and faces point outwards, so each part encloses a positive volume
+        for part in &mesh.parts {
+            let volume: f32 = mesh.faces[part.faces.clone()]
+                .iter()
+                .map(|&[a, b, c]| mesh.vertices[a].dot(&mesh.vertices[b].cross(&mesh.vertices[c])) / 6.0)
+                .sum();
+            assert!(volume > 0.0, "{} is inside out", part.name);
+        }
+
+        let mut vtk = Vec::new();
+        mesh.write_vtk(&mut vtk, true).unwrap();
+        let vtk = String::from_utf8(vtk).unwrap();
+        assert!(vtk.contains(&format!("POINTS {} float", mesh.vertices.len())));
+        assert!(vtk.contains("SCALARS rho_c float 1"));
+        let mut ply = Vec::new();
+        mesh.write_ply(&mut ply, false).unwrap();
+        let ply = String::from_utf8(ply).unwrap();
+        assert_eq!(ply.lines().count(), 10 + mesh.vertices.len() + mesh.faces.len());
+        let mut obj = Vec::new();
+        mesh.write_obj(&mut obj, true).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), mesh.faces.len());
+        assert_eq!(obj.lines().find(|l| l.starts_with("v ")).unwrap().split(' ').count(), 7);
+    }
+
+    #[test]
+    fn test_tube_survives_paths_that_double_back() {
+        let (a, b) = (Vector3::zeros(), Vector3::new(1.0, 0.0, 0.0));
+        let mut mesh = Mesh::default();
+        mesh.tube(&[a, b, a, b], 0.1, 6, VertexValues::default());
+        assert!(!mesh.vertices.is_empty());
+        assert!(mesh.vertices.iter().all(|v| v.iter().all(|c| c.is_finite())));
+    }
+}
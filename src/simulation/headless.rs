@@ -0,0 +1,205 @@
+// src/simulation/headless.rs
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use crate::engines::cspace_engine::CSpaceEngine;
+use crate::engines::observables::Observable;
+use crate::engines::plant_engine::PlantEngine;
+use crate::engines::snapshot::CSpaceSnapshot;
+use crate::plants::tropisms::{Environment, Plant, Tropisms};
+use super::simulation_env::SimulationEnv;
+
+/// Summary of a headless run, averaged over the plants alive at the end
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunMetrics {
+    pub final_rho_c: f32,
+    pub final_energy: f32,
+    pub final_coherence: f32,
+    pub final_distortion: f32,
+    pub light_distance: f32, // Stem tip to light source
+    pub survival_time: f32,  // Time until no viable plant was left (or the full run)
+    pub plant_count: usize,
+    pub state_entropy: f32,     // Final `Observable` values
+    pub metric_volume: f32,
+    pub light_information: f32,
+    pub water_information: f32,
+    pub steps: usize,
+}
+
+impl RunMetrics {
+    /// Names accepted by `get`
+    pub const METRICS: [&'static str; 11] = [
+        "final_rho_c",
+        "final_energy",
+        "final_coherence",
+        "final_distortion",
+        "light_distance",
+        "survival_time",
+        "plant_count",
+        "state_entropy",
+        "metric_volume",
+        "light_information",
+        "water_information",
+    ];
+
+    /// Reads a metric by name
+    pub fn get(&self, name: &str) -> Option<f32> {
+        Some(match name {
+            "final_rho_c" => self.final_rho_c,
+            "final_energy" => self.final_energy,
+            "final_coherence" => self.final_coherence,
+            "final_distortion" => self.final_distortion,
+            "light_distance" => self.light_distance,
+            "survival_time" => self.survival_time,
+            "plant_count" => self.plant_count as f32,
+            "state_entropy" => self.state_entropy,
+            "metric_volume" => self.metric_volume,
+            "light_information" => self.light_information,
+            "water_information" => self.water_information,
+            _ => return None,
+        })
+    }
+}
+
+/// Population averages and observables after one step
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepRecord {
+    pub step: u64,
+    pub time: f32,
+    pub plant_count: usize,
+    pub viable_count: usize,
+    pub mean_rho_c: f32,
+    pub mean_energy: f32,
+    pub mean_coherence: f32,
+    pub mean_distortion: f32,
+    pub observables: Vec<(Observable, f64)>,
+}
+
+impl StepRecord {
+    pub fn of(env: &SimulationEnv, snapshot: &CSpaceSnapshot) -> Self {
+        let n = env.plants.len();
+        let mean = |f: &dyn Fn(&Plant) -> f32| {
+            if n == 0 {
+                0.0
+            } else {
+                env.plants.iter().map(f).sum::<f32>() / n as f32
+            }
+        };
+        StepRecord {
+            step: env.step,
+            time: env.time,
+            plant_count: n,
+            viable_count: env.plants.iter().filter(|p| is_viable(p, &env.environment)).count(),
+            mean_rho_c: if n == 0 { 0.0 } else { snapshot.plants.iter().map(|p| p.rho_c).sum::<f32>() / n as f32 },
+            mean_energy: mean(&|p| p.energy),
+            mean_coherence: mean(&|p| p.coherence),
+            mean_distortion: mean(&|p| p.distortion),
+            observables: snapshot.observables.clone(),
+        }
+    }
+}
+
+impl fmt::Display for StepRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {:>5} t={:.2}: plants={} (viable {}), ρ_c={:.3}, E={:.3}, H={:.3}, D={:.3}",
+            self.step, self.time, self.plant_count, self.viable_count,
+            self.mean_rho_c, self.mean_energy, self.mean_coherence, self.mean_distortion
+        )
+    }
+}
+
+/// Writes one CSV row per record: the averages, then every observable
+pub fn write_steps_csv(path: &Path, records: &[StepRecord]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(out, "step,time,plant_count,viable_count,mean_rho_c,mean_energy,mean_coherence,mean_distortion")?;
+    for o in Observable::ALL {
+        write!(out, ",{}", o)?;
+    }
+    writeln!(out)?;
+    for r in records {
+        write!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            r.step, r.time, r.plant_count, r.viable_count, r.mean_rho_c, r.mean_energy, r.mean_coherence, r.mean_distortion
+        )?;
+        for o in Observable::ALL {
+            let value = r.observables.iter().find(|(k, _)| *k == o).map_or(f64::NAN, |(_, v)| *v);
+            write!(out, ",{}", value)?;
+        }
+        writeln!(out)?;
+    }
+    out.flush()
+}
+
+/// A plant is viable while it has energy and has not collapsed into a Pure Time State
+pub fn is_viable(plant: &Plant, env: &Environment) -> bool {
+    plant.energy > 0.0 && plant.distortion <= env.d_critical
+}
+
+/// Steps `engine` up to `steps` times without any GUI, stopping early once no viable plant is left
+pub fn run_headless(engine: &mut PlantEngine, steps: usize, dt: f32) -> RunMetrics {
+    let start_time = engine.env.time;
+    let mut steps_run = 0;
+    while steps_run < steps {
+        engine.update(dt);
+        steps_run += 1;
+        let env = &engine.env;
+        if !env.plants.iter().any(|p| is_viable(p, &env.environment)) {
+            break;
+        }
+    }
+    summarize(engine, steps_run, engine.env.time - start_time)
+}
+
+fn summarize(engine: &PlantEngine, steps: usize, survival_time: f32) -> RunMetrics {
+    let env = &engine.env;
+    let n = env.plants.len();
+    let mean = |f: &dyn Fn(&Plant) -> f32| {
+        if n == 0 {
+            0.0
+        } else {
+            env.plants.iter().map(f).sum::<f32>() / n as f32
+        }
+    };
+    let light_pos = env.environment.light_pos;
+    let observables = CSpaceEngine::new(env.plants.clone(), env.environment.clone()).observables();
+    let observable = |kind: Observable| {
+        observables.iter().find(|(k, _)| *k == kind).map_or(0.0, |(_, v)| *v as f32)
+    };
+    RunMetrics {
+        final_rho_c: mean(&|p| {
+            Tropisms::compute_complex_density(p.spatial_complexity, p.temporal_complexity, p.energy)
+        }),
+        final_energy: mean(&|p| p.energy),
+        final_coherence: mean(&|p| p.coherence),
+        final_distortion: mean(&|p| p.distortion),
+        light_distance: mean(&|p| (light_pos - (p.pos + p.stem_dir)).norm()),
+        survival_time,
+        plant_count: n,
+        state_entropy: observable(Observable::StateEntropy),
+        metric_volume: observable(Observable::MetricVolume),
+        light_information: observable(Observable::LightInformation),
+        water_information: observable(Observable::WaterInformation),
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::simulation_env::SimulationEnv;
+
+    #[test]
+    fn test_run_headless() {
+        let mut engine = PlantEngine::new(SimulationEnv::with_seed(1));
+        let metrics = run_headless(&mut engine, 50, 0.1);
+        assert_eq!(metrics.steps, 50);
+        assert!((metrics.survival_time - 5.0).abs() < 1e-3);
+        assert_eq!(metrics.get("plant_count"), Some(1.0));
+        assert_eq!(metrics.get("state_entropy"), Some(0.0)); // A single plant has one state
+        assert!(metrics.metric_volume > 0.0);
+    }
+}
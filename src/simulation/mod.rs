@@ -1,4 +1,14 @@
 // src/simulation/mod.rs
+pub mod batch;
+pub mod checkpoint;
+pub mod export;
+pub mod geometry;
+pub mod headless;
+pub mod mutation;
+pub mod parameters;
+pub mod recording;
+pub mod replay;
+pub mod scenario;
 pub mod simulation_env;
 pub mod simulation_runner;
 
@@ -0,0 +1,149 @@
+// src/simulation/mutation.rs
+use std::fmt;
+use nalgebra::Vector3;
+use super::parameters::ParameterError;
+use super::simulation_env::SimulationEnv;
+
+/// A change to a running world, sent to the runner and applied between steps
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mutation {
+    AddPlant { species: String, pos: Vector3<f32> },
+    RemovePlant(usize), // By plant id
+    MoveLight(Vector3<f32>),
+    MoveWater(Vector3<f32>),
+    AddObstacle(Vector3<f32>),
+    RemoveObstacle(usize), // By index into `Environment::obstacles`
+    SetParameter { name: String, value: f32 }, // Any name `SimulationEnv::set_parameter` accepts
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = |p: &Vector3<f32>| format!("({:.2}, {:.2}, {:.2})", p.x, p.y, p.z);
+        match self {
+            Mutation::AddPlant { species, pos } => write!(f, "add {} at {}", species, v(pos)),
+            Mutation::RemovePlant(id) => write!(f, "remove plant {}", id),
+            Mutation::MoveLight(pos) => write!(f, "move light to {}", v(pos)),
+            Mutation::MoveWater(pos) => write!(f, "move water to {}", v(pos)),
+            Mutation::AddObstacle(pos) => write!(f, "add obstacle at {}", v(pos)),
+            Mutation::RemoveObstacle(i) => write!(f, "remove obstacle {}", i),
+            Mutation::SetParameter { name, value } => write!(f, "set {} = {}", name, value),
+        }
+    }
+}
+
+/// What an applied mutation did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationOutcome {
+    Applied,
+    PlantAdded(usize),    // Id of the new plant
+    ObstacleAdded(usize), // Index of the new obstacle
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MutationError {
+    UnknownSpecies(String),
+    NoSuchPlant(usize),
+    NoSuchObstacle(usize),
+    InvalidPosition,
+    Parameter(ParameterError),
+}
+
+impl fmt::Display for MutationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MutationError::UnknownSpecies(name) => write!(f, "unknown species '{}'", name),
+            MutationError::NoSuchPlant(id) => write!(f, "no plant with id {}", id),
+            MutationError::NoSuchObstacle(i) => write!(f, "no obstacle {}", i),
+            MutationError::InvalidPosition => write!(f, "position must be finite"),
+            MutationError::Parameter(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for MutationError {}
+
+impl From<ParameterError> for MutationError {
+    fn from(e: ParameterError) -> Self {
+        MutationError::Parameter(e)
+    }
+}
+
+pub type MutationResult = Result<MutationOutcome, MutationError>;
+
+impl SimulationEnv {
+    /// Applies `mutation`; on error the world is unchanged
+    pub fn apply(&mut self, mutation: &Mutation) -> MutationResult {
+        let finite = |pos: &Vector3<f32>| {
+            if pos.iter().all(|c| c.is_finite()) {
+                Ok(*pos)
+            } else {
+                Err(MutationError::InvalidPosition)
+            }
+        };
+        match mutation {
+            Mutation::AddPlant { species, pos } => {
+                let pos = finite(pos)?;
+                self.spawn_plant(species, pos)
+                    .map(MutationOutcome::PlantAdded)
+                    .ok_or_else(|| MutationError::UnknownSpecies(species.clone()))
+            }
+            Mutation::RemovePlant(id) => {
+                let index = self.plants.iter().position(|p| p.id == *id).ok_or(MutationError::NoSuchPlant(*id))?;
+                self.plants.remove(index);
+                Ok(MutationOutcome::Applied)
+            }
+            Mutation::MoveLight(pos) => {
+                self.environment.light_pos = finite(pos)?;
+                Ok(MutationOutcome::Applied)
+            }
+            Mutation::MoveWater(pos) => {
+                self.environment.water_pos = finite(pos)?;
+                Ok(MutationOutcome::Applied)
+            }
+            Mutation::AddObstacle(pos) => {
+                self.environment.obstacles.push(finite(pos)?);
+                Ok(MutationOutcome::ObstacleAdded(self.environment.obstacles.len() - 1))
+            }
+            Mutation::RemoveObstacle(i) => {
+                if *i >= self.environment.obstacles.len() {
+                    return Err(MutationError::NoSuchObstacle(*i));
+                }
+                self.environment.obstacles.remove(*i);
+                Ok(MutationOutcome::Applied)
+            }
+            Mutation::SetParameter { name, value } => {
+                self.set_parameter(name, *value)?;
+                Ok(MutationOutcome::Applied)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_mutations() {
+        let mut env = SimulationEnv::with_seed(1);
+        let added = env.apply(&Mutation::AddPlant { species: "Deep Root".to_string(), pos: Vector3::new(2.0, 0.0, 0.0) });
+        let Ok(MutationOutcome::PlantAdded(id)) = added else { panic!("plant not added: {:?}", added) };
+        assert!(env.plants.iter().any(|p| p.id == id));
+        assert_eq!(env.apply(&Mutation::RemovePlant(id)), Ok(MutationOutcome::Applied));
+        assert_eq!(env.apply(&Mutation::RemovePlant(id)), Err(MutationError::NoSuchPlant(id)));
+
+        let obstacles = env.environment.obstacles.clone();
+        let added = env.apply(&Mutation::AddObstacle(Vector3::new(1.0, 1.0, 0.0)));
+        assert_eq!(added, Ok(MutationOutcome::ObstacleAdded(obstacles.len())));
+        assert_eq!(env.apply(&Mutation::RemoveObstacle(obstacles.len())), Ok(MutationOutcome::Applied));
+        assert_eq!(env.environment.obstacles, obstacles);
+        assert_eq!(env.apply(&Mutation::RemoveObstacle(obstacles.len())), Err(MutationError::NoSuchObstacle(obstacles.len())));
+        assert_eq!(env.apply(&Mutation::MoveLight(Vector3::new(0.0, f32::NAN, 0.0))), Err(MutationError::InvalidPosition));
+
+        let set = Mutation::SetParameter { name: "alpha".to_string(), value: 0.3 };
+        assert_eq!(env.apply(&set), Ok(MutationOutcome::Applied));
+        assert_eq!(env.environment.alpha, 0.3);
+        let unknown = Mutation::SetParameter { name: "gamma".to_string(), value: 1.0 };
+        assert!(matches!(env.apply(&unknown), Err(MutationError::Parameter(ParameterError::Unknown(_)))));
+    }
+}
@@ -0,0 +1,114 @@
+// src/simulation/parameters.rs
+use std::fmt;
+use crate::plants::metric::MetricModel;
+use crate::plants::species::Genotype;
+use super::simulation_env::SimulationEnv;
+
+/// Environment parameters addressable by name
+pub const ENVIRONMENT_PARAMETERS: [&str; 6] = ["light_intensity", "water_level", "d_critical", "alpha", "beta", "metric_coupling"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterError {
+    Unknown(String),
+    UnknownMetric(String),
+    Invalid { name: String, value: f32, reason: &'static str },
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::Unknown(name) => write!(f, "unknown parameter '{}'", name),
+            ParameterError::UnknownMetric(name) => write!(f, "unknown metric '{}'", name),
+            ParameterError::Invalid { name, value, reason } => {
+                write!(f, "invalid value {} for '{}': {}", value, name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+/// Every name accepted by `SimulationEnv::parameter`: environment parameters, then genotype parameters
+pub fn parameter_names() -> Vec<&'static str> {
+    ENVIRONMENT_PARAMETERS.iter().chain(Genotype::PARAMETERS.iter()).copied().collect()
+}
+
+impl SimulationEnv {
+    /// Reads a named parameter. Genotype parameters are read from the first plant (or first species).
+    pub fn parameter(&self, name: &str) -> Option<f32> {
+        let env = &self.environment;
+        match name {
+            "light_intensity" => Some(env.light_intensity),
+            "water_level" => Some(env.water_level),
+            "d_critical" => Some(env.d_critical),
+            "alpha" => Some(env.alpha),
+            "beta" => Some(env.beta),
+            "metric_coupling" => Some(env.metric.coupling),
+            _ => self
+                .plants
+                .first()
+                .map(|p| p.genotype)
+                .or_else(|| self.species.first().map(|s| s.genotype))
+                .unwrap_or_default()
+                .get(name),
+        }
+    }
+
+    /// Writes a named parameter. Genotype parameters are applied to every plant and species,
+    /// so seedlings inherit the new value.
+    pub fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), ParameterError> {
+        if !value.is_finite() {
+            return Err(ParameterError::Invalid { name: name.to_string(), value, reason: "must be finite" });
+        }
+        let env = &mut self.environment;
+        match name {
+            "light_intensity" => env.light_intensity = value,
+            "water_level" => env.water_level = value,
+            "d_critical" => env.d_critical = value,
+            "alpha" => env.alpha = value,
+            "beta" => env.beta = value,
+            "metric_coupling" => {
+                env.metric = MetricModel::new(env.metric.formulation, value).map_err(|_| ParameterError::Invalid {
+                    name: name.to_string(),
+                    value,
+                    reason: "must be in [0, 0.5)",
+                })?;
+            }
+            _ => {
+                if !Genotype::PARAMETERS.contains(&name) {
+                    return Err(ParameterError::Unknown(name.to_string()));
+                }
+                if value < 0.0 {
+                    return Err(ParameterError::Invalid { name: name.to_string(), value, reason: "must not be negative" });
+                }
+                for plant in &mut self.plants {
+                    plant.genotype.set(name, value);
+                }
+                for species in &mut self.species {
+                    species.genotype.set(name, value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_and_set_parameters() {
+        let mut env = SimulationEnv::with_seed(0);
+        for name in parameter_names() {
+            assert!(env.parameter(name).is_some(), "{}", name);
+        }
+        env.set_parameter("beta", 0.3).unwrap();
+        env.set_parameter("hydrotropism", 0.2).unwrap();
+        assert_eq!(env.environment.beta, 0.3);
+        assert_eq!(env.plants[0].genotype.hydrotropism, 0.2);
+        assert!(env.species.iter().all(|s| s.genotype.hydrotropism == 0.2));
+        assert_eq!(env.set_parameter("gamma", 1.0), Err(ParameterError::Unknown("gamma".to_string())));
+        assert!(env.set_parameter("metric_coupling", 0.5).is_err());
+    }
+}
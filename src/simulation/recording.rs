@@ -0,0 +1,259 @@
+// src/simulation/recording.rs
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use nalgebra::Matrix3;
+use serde::{Deserialize, Serialize};
+use crate::plants::nutrients::NutrientLevels;
+use crate::plants::tropisms::{Environment, Plant};
+use super::simulation_env::SimulationEnv;
+use super::simulation_runner::{SimulationEvent, SimulationObserver};
+
+/// Format version written after the magic bytes
+pub const RECORDING_VERSION: u32 = 1;
+/// First bytes of every recording file
+const MAGIC: &[u8; 8] = b"CSPCREC\0";
+
+/// How the environment is stored in a frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EnvironmentFrame {
+    /// The whole environment
+    Key(Environment),
+    /// Only what steps change on their own: g and the nutrient cells plants drew from
+    Delta { metric_tensor: Matrix3<f32>, nutrients: Vec<(usize, NutrientLevels)> },
+}
+
+/// The world after one step: every plant in full, the environment as a keyframe or a delta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frame {
+    pub step: u64,
+    pub time: f32,
+    pub plants: Vec<Plant>,
+    pub environment: EnvironmentFrame,
+}
+
+impl Frame {
+    /// Brings `environment`, the state after the previous frame, up to this frame
+    pub fn apply(&self, environment: &mut Environment) {
+        match &self.environment {
+            EnvironmentFrame::Key(key) => *environment = key.clone(),
+            EnvironmentFrame::Delta { metric_tensor, nutrients } => {
+                environment.metric_tensor = *metric_tensor;
+                for &(i, levels) in nutrients {
+                    if let Some(cell) = environment.nutrients.cells.get_mut(i) {
+                        *cell = levels;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    NotARecording,
+    UnsupportedVersion(u32),
+    Corrupt(String),
+    Empty,
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(e) => e.fmt(f),
+            RecordingError::NotARecording => write!(f, "not a recording file"),
+            RecordingError::UnsupportedVersion(v) => {
+                write!(f, "recording version {} is not supported (expected {})", v, RECORDING_VERSION)
+            }
+            RecordingError::Corrupt(reason) => write!(f, "corrupt recording: {}", reason),
+            RecordingError::Empty => write!(f, "recording has no frames"),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<io::Error> for RecordingError {
+    fn from(e: io::Error) -> Self {
+        RecordingError::Io(e)
+    }
+}
+
+/// True when only the parts a `Delta` carries differ between `a` and `b`
+fn delta_suffices(a: &Environment, b: &Environment) -> bool {
+    a.light_pos == b.light_pos
+        && a.water_pos == b.water_pos
+        && a.gravity == b.gravity
+        && a.obstacles == b.obstacles
+        && a.light_intensity == b.light_intensity
+        && a.water_level == b.water_level
+        && a.metric == b.metric
+        && a.d_critical == b.d_critical
+        && a.alpha == b.alpha
+        && a.beta == b.beta
+        && a.nutrients.origin == b.nutrients.origin
+        && a.nutrients.cell_size == b.nutrients.cell_size
+        && a.nutrients.cells.len() == b.nutrients.cells.len()
+}
+
+/// Observer that streams a frame to a file after every step. Each frame is flushed as soon
+/// as it is written, so a crash loses at most the frame being written. Attach it with
+/// `ControlCommand::Attach` to be able to stop it; detaching drops it, which flushes the file.
+pub struct Recorder {
+    writer: BufWriter<File>,
+    previous: Option<Environment>,
+    since_keyframe: usize,
+}
+
+impl Recorder {
+    /// Frames between keyframes; seeking replays at most this many deltas
+    pub const KEYFRAME_INTERVAL: usize = 100;
+
+    pub fn create(path: &Path) -> Result<Self, RecordingError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&RECORDING_VERSION.to_le_bytes())?;
+        Ok(Self { writer, previous: None, since_keyframe: 0 })
+    }
+
+    /// Appends the frame for `env` as it is now
+    pub fn record(&mut self, env: &SimulationEnv) -> Result<(), RecordingError> {
+        let current = &env.environment;
+        let environment = match &self.previous {
+            Some(previous) if self.since_keyframe < Self::KEYFRAME_INTERVAL && delta_suffices(previous, current) => {
+                self.since_keyframe += 1;
+                let nutrients = current
+                    .nutrients
+                    .cells
+                    .iter()
+                    .zip(&previous.nutrients.cells)
+                    .enumerate()
+                    .filter(|(_, (now, before))| now != before)
+                    .map(|(i, (now, _))| (i, *now))
+                    .collect();
+                EnvironmentFrame::Delta { metric_tensor: current.metric_tensor, nutrients }
+            }
+            _ => {
+                self.since_keyframe = 1;
+                EnvironmentFrame::Key(current.clone())
+            }
+        };
+        let frame = Frame { step: env.step, time: env.time, plants: env.plants.clone(), environment };
+        bincode::serialize_into(&mut self.writer, &frame).map_err(|e| RecordingError::Corrupt(e.to_string()))?;
+        self.writer.flush()?;
+        self.previous = Some(current.clone());
+        Ok(())
+    }
+}
+
+impl SimulationObserver for Recorder {
+    fn notify(&mut self, env: &SimulationEnv, event: &SimulationEvent) -> bool {
+        if let SimulationEvent::Step(_) = event {
+            if let Err(e) = self.record(env) {
+                eprintln!("Recording stopped: {}", e);
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Every frame of a recording, in step order
+pub struct Recording {
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Reads frames up to the end of the stream; a truncated final frame is dropped
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, RecordingError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(|_| RecordingError::NotARecording)?;
+        if &magic != MAGIC {
+            return Err(RecordingError::NotARecording);
+        }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+        let mut frames = Vec::new();
+        loop {
+            match bincode::deserialize_from::<_, Frame>(&mut reader) {
+                Ok(frame) => frames.push(frame),
+                Err(e) => match *e {
+                    bincode::ErrorKind::Io(ref io) if io.kind() == io::ErrorKind::UnexpectedEof => break,
+                    _ => return Err(RecordingError::Corrupt(e.to_string())),
+                },
+            }
+        }
+        match frames.first() {
+            Some(Frame { environment: EnvironmentFrame::Key(_), .. }) => Ok(Self { frames }),
+            Some(_) => Err(RecordingError::Corrupt("first frame is not a keyframe".to_string())),
+            None => Err(RecordingError::Empty),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, RecordingError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Index of the first frame at or after `step`, clamped to the last frame
+    pub fn index_of(&self, step: u64) -> usize {
+        self.frames.partition_point(|f| f.step < step).min(self.frames.len() - 1)
+    }
+
+    /// The environment at frame `index`, rebuilt from the nearest keyframe at or before it
+    pub fn environment_at(&self, index: usize) -> Environment {
+        let key = self.frames[..=index]
+            .iter()
+            .rposition(|f| matches!(f.environment, EnvironmentFrame::Key(_)))
+            .unwrap_or(0);
+        let EnvironmentFrame::Key(environment) = &self.frames[key].environment else {
+            unreachable!("`read` checks that the first frame is a keyframe")
+        };
+        let mut environment = environment.clone();
+        for frame in &self.frames[key + 1..=index] {
+            frame.apply(&mut environment);
+        }
+        environment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::plant_engine::PlantEngine;
+
+    #[test]
+    fn test_record_and_rebuild() {
+        let path = std::env::temp_dir().join(format!("cs_recording_{}.rec", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        let mut engine = PlantEngine::new(SimulationEnv::with_seed(5));
+        let mut expected = Vec::new();
+        for i in 0..(Recorder::KEYFRAME_INTERVAL + 60) {
+            engine.update(0.1);
+            if i == 40 {
+                engine.env.environment.light_pos.x += 1.0; // Forces a keyframe; the next is due 100 frames later
+            }
+            recorder.record(&engine.env).unwrap();
+            expected.push(engine.env.environment.clone());
+        }
+        // Every frame is on disk while the recorder is still open
+        let recording = Recording::load(&path).unwrap();
+        drop(recorder);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(recording.frames.len(), expected.len());
+        let keyframes = recording.frames.iter().filter(|f| matches!(f.environment, EnvironmentFrame::Key(_))).count();
+        assert_eq!(keyframes, 3);
+        for index in [0, 39, 40, 41, 139, 141, expected.len() - 1] {
+            let environment = recording.environment_at(index);
+            assert_eq!(environment.light_pos, expected[index].light_pos);
+            assert_eq!(environment.nutrients.cells, expected[index].nutrients.cells);
+        }
+        assert_eq!(recording.index_of(42), 41);
+        assert_eq!(recording.index_of(10_000), expected.len() - 1);
+    }
+}
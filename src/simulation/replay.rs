@@ -0,0 +1,287 @@
+// src/simulation/replay.rs
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use crate::engines::cspace_engine::CSpaceEngine;
+use super::recording::Recording;
+use super::simulation_env::SimulationEnv;
+use super::simulation_runner::{
+    frame_duration, ControlCommand, EventSource, ObserverId, RateMeter, RunState, RunnerStatus, SimulationEvent, SimulationObserver,
+    Speed,
+};
+
+/// Plays a `Recording` back to observers in place of a `SimulationRunner`. It takes the same
+/// commands: `Start`/`Resume` play, `Pause`/`Stop` hold, `Step(n)` advances n frames,
+/// `Seek` scrubs to any recorded step (backwards too), `SetSpeed` scales recorded time and
+/// `Reset` rewinds to the first frame. Commands that would change the world are dropped.
+pub struct Replayer {
+    recording: Recording,
+    command_receiver: Receiver<ControlCommand>,
+    observers: Vec<(Option<ObserverId>, Box<dyn SimulationObserver>)>,
+    env: SimulationEnv, // The current frame as a world, for observers
+    index: usize,
+    state: RunState,
+    speed: Speed,
+    pending_steps: u64,
+    next_frame: Instant,
+    rate: RateMeter,
+}
+
+impl Replayer {
+    pub fn new(recording: Recording) -> (Self, Sender<ControlCommand>) {
+        let (command_sender, command_receiver) = channel();
+        let mut replayer = Replayer {
+            recording,
+            command_receiver,
+            observers: Vec::new(),
+            env: SimulationEnv::with_seed(0),
+            index: 0,
+            state: RunState::Stopped,
+            speed: Speed::RealTime(1.0),
+            pending_steps: 0,
+            next_frame: Instant::now(),
+            rate: RateMeter::new(),
+        };
+        replayer.show(0);
+        (replayer, command_sender)
+    }
+
+    pub fn subscribe(&mut self, observer: Box<dyn SimulationObserver>) {
+        self.observers.push((None, observer));
+    }
+
+    pub fn env(&self) -> &SimulationEnv {
+        &self.env
+    }
+
+    pub fn status(&self) -> RunnerStatus {
+        let frames = &self.recording.frames;
+        RunnerStatus {
+            state: self.state,
+            time: self.env.time,
+            step: self.env.step,
+            plant_count: self.env.plants.len(),
+            dt: self.recorded_dt(),
+            speed: self.speed,
+            steps_per_second: self.rate.per_second,
+            timeline: Some((frames[0].step, frames[frames.len() - 1].step)),
+        }
+    }
+
+    /// Average simulated time between recorded frames
+    fn recorded_dt(&self) -> f32 {
+        let frames = &self.recording.frames;
+        match (frames.first(), frames.last()) {
+            (Some(first), Some(last)) if frames.len() > 1 => (last.time - first.time) / (frames.len() - 1) as f32,
+            _ => 0.0,
+        }
+    }
+
+    /// Wall-clock time until the frame after the current one is due
+    fn frame(&self) -> Duration {
+        let frames = &self.recording.frames;
+        let gap = match frames.get(self.index + 1) {
+            Some(next) => (next.time - frames[self.index].time).max(0.0),
+            None => 0.0,
+        };
+        frame_duration(gap, self.speed)
+    }
+
+    fn at_end(&self) -> bool {
+        self.index + 1 >= self.recording.frames.len()
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        thread::spawn(move || self.run())
+    }
+
+    /// Same loop as `SimulationRunner::run`, advancing frames instead of stepping
+    pub fn run(mut self) {
+        loop {
+            let command = if self.pending_steps > 0 {
+                self.command_receiver.recv_timeout(Duration::ZERO)
+            } else if self.state == RunState::Running {
+                self.command_receiver.recv_timeout(self.next_frame.saturating_duration_since(Instant::now()))
+            } else {
+                self.command_receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+            match command {
+                Ok(ControlCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+                Ok(command) => self.handle(command),
+                Err(RecvTimeoutError::Timeout) => self.advance(),
+            }
+        }
+    }
+
+    fn advance(&mut self) {
+        if self.at_end() {
+            self.pending_steps = 0;
+            self.set_state(RunState::Paused);
+            return;
+        }
+        let wait = self.frame();
+        self.show(self.index + 1);
+        if self.rate.tick() && self.state == RunState::Running {
+            self.emit(SimulationEvent::Status(self.status()));
+        }
+        if self.pending_steps > 0 {
+            self.pending_steps -= 1;
+            if self.pending_steps == 0 {
+                self.emit(SimulationEvent::Status(self.status()));
+            }
+        } else {
+            self.next_frame = (self.next_frame + wait).max(Instant::now());
+        }
+    }
+
+    pub fn handle(&mut self, command: ControlCommand) {
+        match command {
+            ControlCommand::Start | ControlCommand::Resume => {
+                if self.at_end() {
+                    self.show(0);
+                }
+                self.set_state(RunState::Running);
+            }
+            ControlCommand::Pause if self.state == RunState::Running => self.set_state(RunState::Paused),
+            ControlCommand::Pause => {}
+            ControlCommand::Stop => {
+                self.pending_steps = 0;
+                self.set_state(RunState::Stopped);
+            }
+            ControlCommand::Step(n) => {
+                self.set_state(RunState::Paused);
+                self.pending_steps += n;
+            }
+            ControlCommand::Seek(step) => {
+                self.pending_steps = 0;
+                self.show(self.recording.index_of(step));
+                self.next_frame = Instant::now();
+                self.emit(SimulationEvent::Status(self.status()));
+            }
+            ControlCommand::Reset => {
+                self.pending_steps = 0;
+                self.state = RunState::Stopped;
+                self.show(0);
+                self.emit(SimulationEvent::Status(self.status()));
+            }
+            ControlCommand::SetSpeed(speed) => {
+                if !matches!(speed, Speed::RealTime(m) if !(m.is_finite() && m > 0.0)) {
+                    self.speed = speed;
+                    self.next_frame = Instant::now();
+                }
+                self.emit(SimulationEvent::Status(self.status()));
+            }
+            ControlCommand::SetDt(_) | ControlCommand::Status => self.emit(SimulationEvent::Status(self.status())),
+            ControlCommand::Subscribe(observer) => self.subscribe(observer),
+            ControlCommand::Attach(id, observer) => self.observers.push((Some(id), observer)),
+            ControlCommand::Detach(id) => self.observers.retain(|(attached, _)| *attached != Some(id)),
+            ControlCommand::Shutdown => self.state = RunState::Stopped,
+            // A recording cannot be changed; dropping any reply channel tells the caller at once
+            ControlCommand::Edit(_) | ControlCommand::Mutate(..) | ControlCommand::Checkpoint(_) | ControlCommand::Restore(_) => {}
+        }
+    }
+
+    fn set_state(&mut self, state: RunState) {
+        if self.state == state {
+            return;
+        }
+        if state == RunState::Running {
+            self.next_frame = Instant::now();
+        } else {
+            self.rate.per_second = 0.0;
+        }
+        self.rate.restart();
+        self.state = state;
+        self.emit(SimulationEvent::Status(self.status()));
+    }
+
+    /// Makes frame `index` current and sends its snapshot
+    fn show(&mut self, index: usize) {
+        let frame = &self.recording.frames[index];
+        let environment = if index == self.index + 1 {
+            let mut environment = self.env.environment.clone();
+            frame.apply(&mut environment);
+            environment
+        } else {
+            self.recording.environment_at(index)
+        };
+        self.index = index;
+        self.env.plants = frame.plants.clone();
+        self.env.environment = environment;
+        self.env.time = frame.time;
+        self.env.step = frame.step;
+
+        let mut engine = CSpaceEngine::new(self.env.plants.clone(), self.env.environment.clone());
+        engine.time = frame.time;
+        let snapshot = engine.update(0.0);
+        self.emit(SimulationEvent::Snapshot(snapshot));
+    }
+
+    fn emit(&mut self, event: SimulationEvent) {
+        let env = &self.env;
+        self.observers.retain_mut(|(_, observer)| observer.notify(env, &event));
+    }
+}
+
+impl EventSource for Replayer {
+    fn subscribe(&mut self, observer: Box<dyn SimulationObserver>) {
+        Replayer::subscribe(self, observer)
+    }
+
+    fn env(&self) -> &SimulationEnv {
+        Replayer::env(self)
+    }
+
+    fn status(&self) -> RunnerStatus {
+        Replayer::status(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::recording::Recorder;
+    use crate::simulation::simulation_runner::SimulationRunner;
+
+    #[test]
+    fn test_replay_seek_and_play() {
+        let path = std::env::temp_dir().join(format!("cs_replay_{}.rec", std::process::id()));
+        let (mut runner, _commands) = SimulationRunner::new(SimulationEnv::with_seed(2));
+        runner.subscribe(Box::new(Recorder::create(&path).unwrap()));
+        let mut positions = Vec::new();
+        for _ in 0..10 {
+            runner.step(0.1);
+            positions.push(runner.env().plants[0].pos);
+        }
+        drop(runner);
+        let recording = Recording::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let (replayer, commands) = Replayer::new(recording);
+        assert_eq!(replayer.status().timeline, Some((1, 10)));
+        let handle = replayer.spawn();
+        let (sender, receiver) = channel();
+        commands.send(ControlCommand::Subscribe(Box::new(sender))).unwrap();
+        commands.send(ControlCommand::Seek(8)).unwrap();
+        commands.send(ControlCommand::Seek(3)).unwrap();
+        commands.send(ControlCommand::Step(2)).unwrap();
+        commands.send(ControlCommand::SetSpeed(Speed::Unlimited)).unwrap();
+        commands.send(ControlCommand::Start).unwrap();
+        let snapshots: Vec<_> = receiver
+            .iter()
+            .filter_map(|event| match event {
+                SimulationEvent::Snapshot(snapshot) => Some(snapshot),
+                _ => None,
+            })
+            .take(1 + 1 + 2 + 5)
+            .collect();
+        commands.send(ControlCommand::Shutdown).unwrap();
+        handle.join().unwrap();
+
+        // Seek forward, seek back, step twice, then play to the end at full speed
+        let steps: Vec<u64> = snapshots.iter().map(|s| (s.time / 0.1).round() as u64).collect();
+        assert_eq!(steps, [8, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(snapshots[0].plants[0].pos, positions[7]);
+        assert_eq!(snapshots.last().unwrap().plants[0].pos, positions[9]);
+    }
+}
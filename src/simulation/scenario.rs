@@ -0,0 +1,331 @@
+// src/simulation/scenario.rs
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use nalgebra::Vector3;
+use serde::Deserialize;
+use toml::Spanned;
+use crate::plants::metric::MetricFormulation;
+use crate::plants::species::Species;
+use super::parameters::ParameterError;
+use super::simulation_env::SimulationEnv;
+
+/// Example scenarios shipped with the simulator, by name (see `scenarios/`)
+pub const EXAMPLES: [(&str, &str); 5] = [
+    ("default", include_str!("../../scenarios/default.toml")),
+    ("competition", include_str!("../../scenarios/competition.toml")),
+    ("drought", include_str!("../../scenarios/drought.toml")),
+    ("maze", include_str!("../../scenarios/maze.toml")),
+    ("coupled", include_str!("../../scenarios/coupled.toml")),
+];
+
+/// Everything needed to build a starting world: the default scene with a seed, founders and overrides
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub description: String,
+    pub seed: u64,
+    pub plants: Vec<(String, Vector3<f32>)>, // Founders by species; empty keeps the default scene's plant
+    pub light: Option<Vector3<f32>>,         // None keeps the default scene's value, as below
+    pub water: Option<Vector3<f32>>,
+    pub gravity: Option<Vector3<f32>>,
+    pub obstacles: Option<Vec<Vector3<f32>>>,
+    pub parameters: Vec<(String, f32)>, // Applied in order with `SimulationEnv::set_parameter`
+    pub metric: Option<MetricFormulation>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            description: String::new(),
+            seed: 42,
+            plants: Vec::new(),
+            light: None,
+            water: None,
+            gravity: None,
+            obstacles: None,
+            parameters: Vec::new(),
+            metric: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioError {
+    UnknownSpecies(String),
+    Parameter(ParameterError),
+    Invalid(String),                               // Malformed TOML or a value out of range
+    Io(String),                                    // The file could not be read
+    At { line: usize, error: Box<ScenarioError> }, // Where in a scenario file the error is
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioError::UnknownSpecies(name) => write!(f, "unknown species '{}'", name),
+            ScenarioError::Parameter(e) => e.fmt(f),
+            ScenarioError::Invalid(reason) => f.write_str(reason),
+            ScenarioError::Io(reason) => write!(f, "cannot read scenario: {}", reason),
+            ScenarioError::At { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<ParameterError> for ScenarioError {
+    fn from(e: ParameterError) -> Self {
+        ScenarioError::Parameter(e)
+    }
+}
+
+/// The file format, before validation; spans point back at the source for errors
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenarioFile {
+    name: Option<String>,
+    #[serde(default)]
+    description: String,
+    seed: Option<u64>,
+    metric: Option<Spanned<String>>,
+    #[serde(default)]
+    environment: EnvironmentSection,
+    #[serde(default)]
+    parameters: BTreeMap<Spanned<String>, Spanned<f32>>,
+    #[serde(default)]
+    plants: Vec<PlantEntry>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct EnvironmentSection {
+    light: Option<Spanned<Vec<f32>>>,
+    water: Option<Spanned<Vec<f32>>>,
+    gravity: Option<Spanned<Vec<f32>>>,
+    obstacles: Option<Vec<Spanned<Vec<f32>>>>,
+    d_critical: Option<Spanned<f32>>, // Shorthand for `parameters.d_critical`
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlantEntry {
+    species: Spanned<String>,
+    pos: Spanned<Vec<f32>>,
+}
+
+impl Scenario {
+    /// Parses a founder as `species@x,y[,z]`, e.g. `Deep Root@-2,0`
+    pub fn parse_plant(spec: &str) -> Result<(String, Vector3<f32>), String> {
+        let (species, pos) = spec
+            .rsplit_once('@')
+            .ok_or_else(|| format!("expected species@x,y[,z], got '{}'", spec))?;
+        let coords: Vec<f32> = pos
+            .split(',')
+            .map(|c| c.trim().parse().map_err(|_| format!("invalid coordinate '{}' in '{}'", c, spec)))
+            .collect::<Result<_, _>>()?;
+        let pos = point(&coords).map_err(|e| format!("{} in '{}'", e, spec))?;
+        Ok((species.trim().to_string(), pos))
+    }
+
+    /// Parses and validates a TOML scenario; every error carries the line it refers to
+    pub fn from_toml(source: &str) -> Result<Self, ScenarioError> {
+        let line = |offset: usize| source[..offset.min(source.len())].matches('\n').count() + 1;
+        let at = |span: std::ops::Range<usize>, error: ScenarioError| ScenarioError::At { line: line(span.start), error: Box::new(error) };
+        let file: ScenarioFile = toml::from_str(source).map_err(|e| {
+            let error = ScenarioError::Invalid(e.message().to_string());
+            match e.span() {
+                Some(span) => at(span, error),
+                None => error,
+            }
+        })?;
+        let vector = |v: &Spanned<Vec<f32>>| point(v.get_ref()).map_err(|e| at(v.span(), ScenarioError::Invalid(e)));
+
+        let mut scenario = Scenario {
+            name: file.name.unwrap_or_else(|| "untitled".to_string()),
+            description: file.description,
+            seed: file.seed.unwrap_or(Scenario::default().seed),
+            ..Scenario::default()
+        };
+        if let Some(metric) = &file.metric {
+            let formulation = MetricFormulation::parse(metric.get_ref())
+                .ok_or_else(|| at(metric.span(), ScenarioError::Invalid(format!("unknown metric formulation '{}'", metric.get_ref()))))?;
+            scenario.metric = Some(formulation);
+        }
+
+        let env = &file.environment;
+        scenario.light = env.light.as_ref().map(vector).transpose()?;
+        scenario.water = env.water.as_ref().map(vector).transpose()?;
+        if let Some(gravity) = &env.gravity {
+            let g = vector(gravity)?;
+            if g.norm() == 0.0 {
+                return Err(at(gravity.span(), ScenarioError::Invalid("gravity must not be zero".to_string())));
+            }
+            scenario.gravity = Some(g);
+        }
+        scenario.obstacles = env.obstacles.as_ref().map(|o| o.iter().map(vector).collect()).transpose()?;
+
+        // Check names and values against a scratch world so mistakes point at their line
+        let mut check = SimulationEnv::bare(0);
+        // The table comes back sorted by name; put it back in file order so it applies that way
+        let mut table: Vec<_> = file.parameters.into_iter().collect();
+        table.sort_by_key(|(name, _)| name.span().start);
+        let parameters = env
+            .d_critical
+            .as_ref()
+            .map(|v| (Spanned::new(v.span(), "d_critical".to_string()), v.clone()))
+            .into_iter()
+            .chain(table);
+        for (name, value) in parameters {
+            check.set_parameter(name.get_ref(), *value.get_ref()).map_err(|e| at(name.span(), e.into()))?;
+            scenario.parameters.push((name.into_inner(), value.into_inner()));
+        }
+
+        let species = Species::catalog();
+        for plant in file.plants {
+            if !species.iter().any(|s| &s.name == plant.species.get_ref()) {
+                return Err(at(plant.species.span(), ScenarioError::UnknownSpecies(plant.species.into_inner())));
+            }
+            let pos = vector(&plant.pos)?;
+            scenario.plants.push((plant.species.into_inner(), pos));
+        }
+        Ok(scenario)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ScenarioError> {
+        let source = fs::read_to_string(path).map_err(|e| ScenarioError::Io(format!("{}: {}", path.display(), e)))?;
+        Self::from_toml(&source)
+    }
+
+    /// One of the bundled `EXAMPLES`
+    pub fn example(name: &str) -> Option<Self> {
+        EXAMPLES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, source)| Self::from_toml(source).expect("bundled scenarios are valid"))
+    }
+
+    /// A bundled example by name, otherwise a scenario file
+    pub fn open(name_or_path: &str) -> Result<Self, ScenarioError> {
+        match Self::example(name_or_path) {
+            Some(scenario) => Ok(scenario),
+            None => Self::load(Path::new(name_or_path)),
+        }
+    }
+
+    pub fn build(&self) -> Result<SimulationEnv, ScenarioError> {
+        let mut env = SimulationEnv::with_seed(self.seed);
+        self.apply(&mut env)?;
+        Ok(env)
+    }
+
+    /// Lays this scenario over `env`, keeping whatever it leaves unset; the seed is not touched
+    pub(crate) fn apply(&self, env: &mut SimulationEnv) -> Result<(), ScenarioError> {
+        if !self.plants.is_empty() {
+            env.plants.clear();
+            env.next_plant_id = 0;
+            for (species, pos) in &self.plants {
+                env.spawn_plant(species, *pos).ok_or_else(|| ScenarioError::UnknownSpecies(species.clone()))?;
+            }
+        }
+        let environment = &mut env.environment;
+        if let Some(light) = self.light {
+            environment.light_pos = light;
+        }
+        if let Some(water) = self.water {
+            environment.water_pos = water;
+        }
+        if let Some(gravity) = self.gravity {
+            environment.gravity = gravity;
+        }
+        if let Some(obstacles) = &self.obstacles {
+            environment.obstacles = obstacles.clone();
+        }
+        if let Some(formulation) = self.metric {
+            environment.metric.formulation = formulation;
+        }
+        for (name, value) in &self.parameters {
+            env.set_parameter(name, *value)?;
+        }
+        Ok(())
+    }
+}
+
+/// A position from 2 (z = 0) or 3 finite coordinates
+fn point(coords: &[f32]) -> Result<Vector3<f32>, String> {
+    let pos = match coords[..] {
+        [x, y] => Vector3::new(x, y, 0.0),
+        [x, y, z] => Vector3::new(x, y, z),
+        _ => return Err(format!("expected 2 or 3 coordinates, got {}", coords.len())),
+    };
+    if pos.iter().all(|c| c.is_finite()) {
+        Ok(pos)
+    } else {
+        Err("coordinates must be finite".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_scenario() {
+        let scenario = Scenario {
+            seed: 7,
+            plants: vec![Scenario::parse_plant("Deep Root@-2,0").unwrap(), Scenario::parse_plant("Deep Root@1,0,0.5").unwrap()],
+            parameters: vec![("alpha".to_string(), 0.2)],
+            metric: Some(MetricFormulation::Coupled),
+            ..Scenario::default()
+        };
+        let env = scenario.build().unwrap();
+        assert_eq!(env.plants.len(), 2);
+        assert_eq!((env.plants[0].id, env.plants[1].id), (0, 1));
+        assert_eq!(env.plants[1].pos, Vector3::new(1.0, 0.0, 0.5));
+        assert_eq!(env.environment.alpha, 0.2);
+        assert_eq!(env.environment.metric.formulation, MetricFormulation::Coupled);
+
+        let unknown = Scenario { plants: vec![("Fern".to_string(), Vector3::zeros())], ..Scenario::default() };
+        assert_eq!(unknown.build().err(), Some(ScenarioError::UnknownSpecies("Fern".to_string())));
+        assert!(Scenario::parse_plant("Deep Root@1").is_err());
+    }
+
+    #[test]
+    fn test_examples_build() {
+        for (name, _) in EXAMPLES {
+            let scenario = Scenario::example(name).unwrap();
+            assert_eq!(scenario.name, name);
+            assert!(scenario.build().is_ok(), "{}", name);
+        }
+        // The default example is the scene `SimulationEnv::with_seed` builds
+        let example = Scenario::example("default").unwrap().build().unwrap();
+        let builtin = SimulationEnv::with_seed(42);
+        assert_eq!(example.environment.light_pos, builtin.environment.light_pos);
+        assert_eq!(example.environment.water_pos, builtin.environment.water_pos);
+        assert_eq!(example.environment.obstacles, builtin.environment.obstacles);
+        assert_eq!(example.plants[0].pos, builtin.plants[0].pos);
+        assert_eq!(builtin.environment.d_critical, 10.0);
+    }
+
+    #[test]
+    fn test_parameters_keep_file_order() {
+        let scenario = Scenario::from_toml("[parameters]\nbeta = 0.2\nalpha = 0.3\nd_critical = 4.0\n").unwrap();
+        let names: Vec<&str> = scenario.parameters.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["beta", "alpha", "d_critical"]);
+    }
+
+    #[test]
+    fn test_errors_point_at_lines() {
+        let line_of = |source: &str| match Scenario::from_toml(source) {
+            Err(ScenarioError::At { line, .. }) => line,
+            other => panic!("expected a located error, got {:?}", other),
+        };
+        assert_eq!(line_of("seed = 1\n\n[[plants]]\nspecies = \"Fern\"\npos = [0.0, 0.0]\n"), 4);
+        assert_eq!(line_of("[[plants]]\nspecies = \"Deep Root\"\npos = [0.0]\n"), 3);
+        assert_eq!(line_of("[parameters]\nalpha = 0.1\ngamma = 2.0\n"), 3);
+        assert_eq!(line_of("[environment]\nlight = [1.0, 2.0]\nligth = [0.0, 0.0]\n"), 3);
+        assert_eq!(line_of("seed = 1\nmetric = \"curved\"\n"), 2);
+        assert_eq!(line_of("seed = \"one\"\n"), 1);
+    }
+}
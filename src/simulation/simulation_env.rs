@@ -1,46 +1,110 @@
 // src/simulation/simulation_env.rs
 use nalgebra::{Vector3, Matrix3};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use crate::plants::metric::MetricModel;
+use crate::plants::nutrients::NutrientField;
+use crate::plants::species::Species;
 use crate::plants::tropisms::{Plant, Environment};
+use super::scenario::Scenario;
 
+/// The whole simulated world; serializable so it can be checkpointed
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SimulationEnv {
     pub plants: Vec<Plant>,
     pub environment: Environment,
+    pub species: Vec<Species>, // Species that can be planted in this simulation
+    pub max_plants: usize,     // Carrying capacity; seeding stops when reached
+    pub next_plant_id: usize,
     pub time: f32, // Tracks simulation time
+    pub step: u64, // Number of completed steps
+    pub seed: u64, // Seed the RNG was created from
+    pub rng: ChaCha8Rng, // Drives noise and seeding so runs are reproducible
+}
+
+impl Default for SimulationEnv {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SimulationEnv {
     pub fn new() -> Self {
-        let plants = vec![
-            Plant {
-                pos: Vector3::new(0.0, 0.0, 0.0),
-                stem_dir: Vector3::new(0.0, 1.0, 0.0), // Upward
-                root_dir: Vector3::new(0.0, -1.0, 0.0), // Downward
-                energy: 10.0,
-                coherence: 1.0,
-                distortion: 0.0,
-                temporal_complexity: 0.0,
-                spatial_complexity: 0.0,
-            },
-        ];
+        Self::with_seed(rand::thread_rng().gen())
+    }
+
+    /// Builds the default scene (`scenarios/default.toml`) with a fixed seed, so two envs with the
+    /// same seed evolve identically
+    pub fn with_seed(seed: u64) -> Self {
+        let mut env = Self::bare(seed);
+        let default = Scenario::example("default").expect("the default scenario is bundled");
+        default.apply(&mut env).expect("the default scenario is valid");
+        env
+    }
+
+    /// A world with no plants or obstacles, light and water at the origin and default parameters;
+    /// scenarios are laid out on top of it
+    pub(crate) fn bare(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
         let environment = Environment {
-            light_pos: Vector3::new(5.0, 5.0, 0.0),
-            water_pos: Vector3::new(2.0, -2.0, 0.0),
-            gravity: Vector3::new(0.0, -1.0, 0.0),
-            obstacles: vec![Vector3::new(1.0, 0.0, 0.0)],
+            light_pos: Vector3::zeros(),
+            water_pos: Vector3::zeros(),
+            gravity: -Vector3::y(),
+            obstacles: Vec::new(),
             light_intensity: 1.0,
             water_level: 1.0,
             metric_tensor: Matrix3::identity(),
+            metric: MetricModel::default(),
             d_critical: 10.0,
+            alpha: 0.05,
+            beta: 0.1,
+            // Soil spans x in [-10, 10], y in [-10, 0]
+            nutrients: NutrientField::patchy(
+                &mut rng,
+                Vector3::new(-10.0, -10.0, 0.0),
+                0.5,
+                40,
+                20,
+                6,
+            ),
         };
         Self {
-            plants,
+            plants: Vec::new(),
             environment,
+            species: Species::catalog(),
+            max_plants: 32,
+            next_plant_id: 0,
             time: 0.0,
+            step: 0,
+            seed,
+            rng,
         }
     }
 
+    /// Restarts the RNG stream from `seed`
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
     pub fn update_time(&mut self, dt: f32) {
         self.time += dt;
+        self.step += 1;
+    }
+
+    /// Looks up one of this simulation's species by name
+    pub fn species_by_name(&self, name: &str) -> Option<&Species> {
+        self.species.iter().find(|s| s.name == name)
+    }
+
+    /// Plants a founder of the named species at `pos`, returning its id
+    pub fn spawn_plant(&mut self, species: &str, pos: Vector3<f32>) -> Option<usize> {
+        let id = self.next_plant_id;
+        let plant = self.species_by_name(species)?.instantiate(id, pos);
+        self.next_plant_id += 1;
+        self.plants.push(plant);
+        Some(id)
     }
 }
 
@@ -54,5 +118,15 @@ mod tests {
         assert_eq!(env.plants.len(), 1);
         assert_eq!(env.environment.light_pos, Vector3::new(5.0, 5.0, 0.0));
         assert_eq!(env.time, 0.0);
+        assert_eq!(env.step, 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_spawn_plant() {
+        let mut env = SimulationEnv::new();
+        let id = env.spawn_plant("Deep Root", Vector3::new(-2.0, 0.0, 0.0)).unwrap();
+        assert_eq!(id, 1);
+        assert_eq!(env.plants[1].species, "Deep Root");
+        assert!(env.spawn_plant("No Such Species", Vector3::zeros()).is_none());
+    }
+}
@@ -1,140 +1,585 @@
-use std::sync::{Arc, Mutex};
-use std::sync::mpsc::{channel, Sender, Receiver};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
-use crate::control::SimulationControl;
 use crate::engines::cspace_engine::CSpaceEngine;
 use crate::engines::plant_engine::PlantEngine;
+use crate::engines::snapshot::CSpaceSnapshot;
 use crate::plants::tropisms::TropismResult;
+use super::checkpoint::Checkpoint;
+use super::mutation::{Mutation, MutationResult};
+use super::simulation_env::SimulationEnv;
+
+/// A change to the world, applied by the runner between steps
+pub type Edit = Box<dyn FnOnce(&mut SimulationEnv) + Send>;
 
 pub enum ControlCommand {
-    Start,
-    Stop,
-    Status,
-    Reset,
+    Start,    // Begin stepping from Stopped or Paused; no-op if already running
+    Stop,     // Stop after the current step and drop any pending `Step`s; the world is kept
+    Pause,    // Suspend a running simulation
+    Resume,   // Continue a paused simulation; no-op unless paused
+    Step(u64), // Pause, then run this many steps as fast as possible
+    SetDt(f32),
+    SetSpeed(Speed),
+    Seek(u64), // Jump to a recorded step; only a replay can seek, the live runner just reports its status
+    Status,   // Report a `RunnerStatus`
+    Reset,    // Stop and go back to the world the runner started with or last restored
+    Shutdown, // Stop and return from `run`
+    Subscribe(Box<dyn SimulationObserver>),
+    /// Subscribe an observer under an id, so `Detach` can remove it later
+    Attach(ObserverId, Box<dyn SimulationObserver>),
+    /// Drop the observer attached under this id at once, so its `Drop` flushes or finalizes output now
+    Detach(ObserverId),
+    Edit(Edit),
+    /// Apply a mutation between steps; the result goes to the optional reply channel and to observers
+    Mutate(Mutation, Option<Sender<MutationResult>>),
+    /// Send a checkpoint of the world and timestep as of the last completed step
+    Checkpoint(Sender<Checkpoint>),
+    /// Replace the world and timestep; the run state is kept, so a running simulation carries on
+    Restore(Box<Checkpoint>),
+}
+
+/// Names an observer attached with `ControlCommand::Attach`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+impl ObserverId {
+    /// An id no other call in this process returns
+    pub fn fresh() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        ObserverId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    Stopped,
+    Running,
+    Paused,
+}
+
+impl fmt::Display for RunState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RunState::Stopped => "Stopped",
+            RunState::Running => "Running",
+            RunState::Paused => "Paused",
+        })
+    }
+}
+
+/// How fast simulated time runs while the runner is running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    RealTime(f32), // Simulated seconds per wall-clock second
+    Unlimited,     // Step as fast as possible
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Speed::RealTime(multiplier) => write!(f, "{}x", multiplier),
+            Speed::Unlimited => write!(f, "max"),
+        }
+    }
+}
+
+/// Runner status, reported in response to `ControlCommand::Status`, after every state change
+/// and about once a second while running
+#[derive(Debug, Clone)]
+pub struct RunnerStatus {
+    pub state: RunState,
+    pub time: f32,
+    pub step: u64,
+    pub plant_count: usize,
+    pub dt: f32,
+    pub speed: Speed,
+    pub steps_per_second: f32, // Measured over the last second of stepping; 0 once stopped or paused
+    pub timeline: Option<(u64, u64)>, // First and last recorded step when replaying; None for a live run
+}
+
+impl RunnerStatus {
+    pub fn running(&self) -> bool {
+        self.state == RunState::Running
+    }
+
+    /// Simulated seconds per wall-clock second actually achieved
+    pub fn sim_rate(&self) -> f32 {
+        self.steps_per_second * self.dt
+    }
+}
+
+impl fmt::Display for RunnerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Status: {} (t={:.2}, step={}, plants={}, dt={}, speed={}, {:.1} steps/s = {:.2}x)",
+            self.state,
+            self.time,
+            self.step,
+            self.plant_count,
+            self.dt,
+            self.speed,
+            self.steps_per_second,
+            self.sim_rate()
+        )?;
+        match self.timeline {
+            Some((first, last)) => write!(f, " replaying steps {}..={}", first, last),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Events the runner sends to its listeners
+#[derive(Debug, Clone)]
+pub enum SimulationEvent {
+    /// Tropism results for one step, grouped per plant
+    Step(Vec<Vec<TropismResult>>),
+    /// Manifold state after the same step, or after an edit or reset
+    Snapshot(CSpaceSnapshot),
+    Status(RunnerStatus),
+    /// A mutation and what came of it, sent before the snapshot showing it
+    Mutation(Mutation, MutationResult),
+}
+
+/// Anything attached to the runner: GUI mirrors, channels, exporters
+pub trait SimulationObserver: Send {
+    /// Called for every event, in order, with the world as it is after the event.
+    /// Return false to unsubscribe.
+    fn notify(&mut self, env: &SimulationEnv, event: &SimulationEvent) -> bool;
+}
+
+/// A channel subscriber; it unsubscribes once the receiver is dropped
+impl SimulationObserver for Sender<SimulationEvent> {
+    fn notify(&mut self, _env: &SimulationEnv, event: &SimulationEvent) -> bool {
+        self.send(event.clone()).is_ok()
+    }
+}
+
+/// What a `SimulationControl` attaches to: the live runner or a replay
+pub trait EventSource {
+    fn subscribe(&mut self, observer: Box<dyn SimulationObserver>);
+    fn env(&self) -> &SimulationEnv;
+    fn status(&self) -> RunnerStatus;
+}
+
+impl EventSource for SimulationRunner {
+    fn subscribe(&mut self, observer: Box<dyn SimulationObserver>) {
+        SimulationRunner::subscribe(self, observer)
+    }
+
+    fn env(&self) -> &SimulationEnv {
+        SimulationRunner::env(self)
+    }
+
+    fn status(&self) -> RunnerStatus {
+        SimulationRunner::status(self)
+    }
+}
+
+/// Longest wait between steps; a tiny speed multiplier waits this long instead of overflowing
+const MAX_FRAME: Duration = Duration::from_secs(3600);
+
+/// Wall-clock time that `seconds` of simulated time take at `speed`
+pub(crate) fn frame_duration(seconds: f32, speed: Speed) -> Duration {
+    match speed {
+        Speed::RealTime(multiplier) => {
+            Duration::try_from_secs_f32(seconds / multiplier).map_or(MAX_FRAME, |d| d.min(MAX_FRAME))
+        }
+        Speed::Unlimited => Duration::ZERO,
+    }
+}
+
+/// Steps per wall-clock second, measured over windows of about a second
+pub(crate) struct RateMeter {
+    started: Instant,
+    steps: u64,
+    pub per_second: f32,
+}
+
+impl RateMeter {
+    pub fn new() -> Self {
+        Self { started: Instant::now(), steps: 0, per_second: 0.0 }
+    }
+
+    pub fn restart(&mut self) {
+        self.started = Instant::now();
+        self.steps = 0;
+    }
+
+    /// Counts one step; true when a window closed and `per_second` was updated
+    pub fn tick(&mut self) -> bool {
+        self.steps += 1;
+        let elapsed = self.started.elapsed();
+        if elapsed < Duration::from_secs(1) {
+            return false;
+        }
+        self.per_second = self.steps as f32 / elapsed.as_secs_f32();
+        self.restart();
+        true
+    }
+}
+
+/// The simulation core: owns the engines and is the only thing that steps them
 pub struct SimulationRunner {
-    control: Arc<SimulationControl>,
     command_receiver: Receiver<ControlCommand>,
-    command_sender: Sender<ControlCommand>,
-    log_sender: Sender<Vec<Vec<TropismResult>>>,
-    running: Arc<Mutex<bool>>,
-    plant_engine: Arc<Mutex<PlantEngine>>,
-    cspace_engine: Arc<Mutex<CSpaceEngine>>,
+    observers: Vec<(Option<ObserverId>, Box<dyn SimulationObserver>)>, // Id if attached rather than subscribed
+    state: RunState,
+    dt: f32,
+    speed: Speed,
+    pending_steps: u64, // Left to run from `Step(n)`
+    next_step: Instant, // When the next step is due while running
+    rate: RateMeter,
+    plant_engine: PlantEngine,
+    cspace_engine: CSpaceEngine,
+    initial: SimulationEnv, // What `Reset` goes back to: the loaded scenario, seed included
 }
 
 impl SimulationRunner {
-    pub fn new(control: Arc<SimulationControl>) -> (Self, Sender<ControlCommand>, Receiver<Vec<Vec<TropismResult>>>) {
-        let (command_sender, command_receiver) = channel();
-        let (log_sender, log_receiver) = channel();
-        let running = Arc::new(Mutex::new(false));
-
-        let plant_engine = control.engine().clone();
-        let cspace_engine = Arc::new(Mutex::new(CSpaceEngine::new(
-            plant_engine.lock().unwrap().env.plants.clone(),
-            plant_engine.lock().unwrap().env.environment.clone(),
-        )));
+    /// Default timestep; at `Speed::RealTime(1.0)` that is one step every 100 ms
+    pub const DT: f32 = 0.1;
 
+    pub fn new(env: SimulationEnv) -> (Self, Sender<ControlCommand>) {
+        let (command_sender, command_receiver) = channel();
+        let mut cspace_engine = CSpaceEngine::new(env.plants.clone(), env.environment.clone());
+        cspace_engine.time = env.time;
         let runner = SimulationRunner {
-            control,
             command_receiver,
-            command_sender: command_sender.clone(),
-            log_sender,
-            running,
-            plant_engine,
+            observers: Vec::new(),
+            state: RunState::Stopped,
+            dt: Self::DT,
+            speed: Speed::RealTime(1.0),
+            pending_steps: 0,
+            next_step: Instant::now(),
+            rate: RateMeter::new(),
+            initial: env.clone(),
+            plant_engine: PlantEngine::new(env),
             cspace_engine,
         };
-        (runner, command_sender, log_receiver)
+        (runner, command_sender)
     }
 
-    pub fn run(&self) {
-        let target_dt = Duration::from_millis(100); // 10 FPS
-        let mut last_update = Instant::now();
-        println!("SimulationRunner event loop started.");
+    pub fn subscribe(&mut self, observer: Box<dyn SimulationObserver>) {
+        self.observers.push((None, observer));
+    }
+
+    pub fn env(&self) -> &SimulationEnv {
+        &self.plant_engine.env
+    }
 
+    pub fn status(&self) -> RunnerStatus {
+        let env = &self.plant_engine.env;
+        RunnerStatus {
+            state: self.state,
+            time: env.time,
+            step: env.step,
+            plant_count: env.plants.len(),
+            dt: self.dt,
+            speed: self.speed,
+            steps_per_second: self.rate.per_second,
+            timeline: None,
+        }
+    }
+
+    /// Wall-clock time between steps while running
+    fn frame(&self) -> Duration {
+        frame_duration(self.dt, self.speed)
+    }
+
+    /// Runs the loop on its own thread
+    pub fn spawn(self) -> JoinHandle<()> {
+        thread::spawn(move || self.run())
+    }
+
+    /// Handles commands and steps while running or while `Step`s are pending. Returns on
+    /// `Shutdown` or once every command sender is gone.
+    pub fn run(mut self) {
         loop {
-            let now = Instant::now();
-            let _elapsed = now.duration_since(last_update); // Unused for now
-
-            // Process commands
-            while let Ok(command) = self.command_receiver.try_recv() {
-                match command {
-                    ControlCommand::Start => {
-                        let mut running = self.running.lock().unwrap();
-                        if !*running {
-                            *running = true;
-                            println!("Simulation started.");
-                        }
-                    }
-                    ControlCommand::Stop => {
-                        let mut running = self.running.lock().unwrap();
-                        if *running {
-                            *running = false;
-                            println!("Simulation stopped.");
+            // Block while idle; otherwise wait no longer than the next step is due
+            let command = if self.pending_steps > 0 {
+                self.command_receiver.recv_timeout(Duration::ZERO)
+            } else if self.state == RunState::Running {
+                self.command_receiver.recv_timeout(self.next_step.saturating_duration_since(Instant::now()))
+            } else {
+                self.command_receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            };
+            match command {
+                Ok(ControlCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+                Ok(command) => self.handle(command),
+                Err(RecvTimeoutError::Timeout) => {
+                    self.step(self.dt);
+                    if self.pending_steps > 0 {
+                        self.pending_steps -= 1;
+                        if self.pending_steps == 0 {
+                            self.emit(SimulationEvent::Status(self.status()));
                         }
-                    }
-                    ControlCommand::Status => {
-                        let running = *self.running.lock().unwrap();
-                        let status = if running { "Running" } else { "Stopped" };
-                        println!("Simulation status: {}", status);
-                        self.log_sender.send(vec![vec![TropismResult {
-                            growth_delta: nalgebra::Vector3::zeros(),
-                            rho_c: 0.0,
-                            log: format!("Status: {}", status),
-                        }]]).unwrap();
-                    }
-                    ControlCommand::Reset => {
-                        let mut running = self.running.lock().unwrap();
-                        *running = false;
-                        let mut plant_engine = self.plant_engine.lock().unwrap();
-                        let mut cspace_engine = self.cspace_engine.lock().unwrap();
-                        plant_engine.env = crate::simulation::simulation_env::SimulationEnv::new();
-                        cspace_engine.plants = plant_engine.env.plants.clone();
-                        cspace_engine.environment = plant_engine.env.environment.clone();
-                        println!("Simulation reset.");
+                    } else {
+                        self.next_step = (self.next_step + self.frame()).max(Instant::now());
                     }
                 }
             }
+        }
+        self.state = RunState::Stopped;
+    }
 
-            // Update simulation if running
-            if *self.running.lock().unwrap() {
-                let dt = 0.1; // Fixed timestep
-                let mut plant_engine = self.plant_engine.lock().unwrap();
-                let mut cspace_engine = self.cspace_engine.lock().unwrap();
-
-                // Step 1: Update PlantEngine (tropisms)
-                let results = plant_engine.update(dt);
-
-                // Step 2: Update CSpaceEngine (manifold properties)
-                cspace_engine.plants = plant_engine.env.plants.clone();
-                cspace_engine.environment = plant_engine.env.environment.clone();
-                cspace_engine.update(dt);
-
-                // Send logs
-                if let Err(e) = self.log_sender.send(results.clone()) {
-                    eprintln!("Failed to send simulation results: {}", e);
-                    break;
+    /// Applies one command. `Shutdown` only has an effect in `run`.
+    pub fn handle(&mut self, command: ControlCommand) {
+        match command {
+            ControlCommand::Start => self.set_state(RunState::Running),
+            ControlCommand::Stop => {
+                self.pending_steps = 0;
+                self.set_state(RunState::Stopped);
+            }
+            ControlCommand::Pause if self.state == RunState::Running => self.set_state(RunState::Paused),
+            ControlCommand::Resume if self.state == RunState::Paused => self.set_state(RunState::Running),
+            ControlCommand::Pause | ControlCommand::Resume => {}
+            ControlCommand::Step(n) => {
+                self.set_state(RunState::Paused);
+                if self.pending_steps == 0 {
+                    self.rate.restart();
                 }
-
-                // Update shared state in SimulationControl
-                {
-                    let plants_guard = self.control.plants();
-                    let mut plants = plants_guard.lock().unwrap();
-                    *plants = plant_engine.env.plants.clone();
+                self.pending_steps += n;
+            }
+            ControlCommand::SetDt(dt) => {
+                if dt.is_finite() && dt > 0.0 {
+                    self.dt = dt;
                 }
-                {
-                    let env_guard = self.control.environment();
-                    let mut env = env_guard.lock().unwrap();
-                    *env = plant_engine.env.environment.clone();
+                self.emit(SimulationEvent::Status(self.status()));
+            }
+            ControlCommand::SetSpeed(speed) => {
+                if !matches!(speed, Speed::RealTime(m) if !(m.is_finite() && m > 0.0)) {
+                    self.speed = speed;
+                    self.next_step = Instant::now();
                 }
+                self.emit(SimulationEvent::Status(self.status()));
             }
-
-            // Timing control
-            let frame_time = now.elapsed();
-            if frame_time < target_dt {
-                std::thread::sleep(target_dt - frame_time);
+            ControlCommand::Status | ControlCommand::Seek(_) => self.emit(SimulationEvent::Status(self.status())),
+            ControlCommand::Reset => {
+                self.state = RunState::Stopped;
+                self.pending_steps = 0;
+                self.load(self.initial.clone());
+                self.emit(SimulationEvent::Status(self.status()));
+                self.publish();
+            }
+            ControlCommand::Shutdown => self.state = RunState::Stopped,
+            ControlCommand::Subscribe(observer) => self.subscribe(observer),
+            ControlCommand::Attach(id, observer) => self.observers.push((Some(id), observer)),
+            ControlCommand::Detach(id) => self.observers.retain(|(attached, _)| *attached != Some(id)),
+            ControlCommand::Edit(edit) => {
+                edit(&mut self.plant_engine.env);
+                self.publish();
+            }
+            ControlCommand::Checkpoint(reply) => {
+                let _ = reply.send(self.checkpoint());
+            }
+            ControlCommand::Restore(checkpoint) => {
+                self.pending_steps = 0;
+                if checkpoint.dt.is_finite() && checkpoint.dt > 0.0 {
+                    self.dt = checkpoint.dt;
+                }
+                self.initial = checkpoint.env.clone();
+                self.load(checkpoint.env);
+                self.emit(SimulationEvent::Status(self.status()));
+                self.publish();
             }
-            last_update = now;
+            ControlCommand::Mutate(mutation, reply) => {
+                let result = self.plant_engine.env.apply(&mutation);
+                if let Some(reply) = reply {
+                    let _ = reply.send(result.clone());
+                }
+                let applied = result.is_ok();
+                self.emit(SimulationEvent::Mutation(mutation, result));
+                if applied {
+                    self.publish();
+                }
+            }
+        }
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { env: self.plant_engine.env.clone(), dt: self.dt }
+    }
+
+    /// Replaces the world, rebuilding the C-Space engine so its clock matches
+    fn load(&mut self, env: SimulationEnv) {
+        self.cspace_engine = CSpaceEngine::new(env.plants.clone(), env.environment.clone());
+        self.cspace_engine.time = env.time;
+        self.plant_engine.env = env;
+    }
+
+    fn set_state(&mut self, state: RunState) {
+        if self.state == state {
+            return;
+        }
+        if state == RunState::Running {
+            self.next_step = Instant::now();
+        } else {
+            self.rate.per_second = 0.0;
+        }
+        self.rate.restart();
+        self.state = state;
+        self.emit(SimulationEvent::Status(self.status()));
+    }
+
+    /// Sends a snapshot of the world as it is, without stepping, so observers see edits and resets
+    fn publish(&mut self) {
+        self.cspace_engine.plants = self.plant_engine.env.plants.clone();
+        self.cspace_engine.environment = self.plant_engine.env.environment.clone();
+        let snapshot = self.cspace_engine.snapshot();
+        self.emit(SimulationEvent::Snapshot(snapshot));
+    }
+
+    /// Advances both engines by `dt` and notifies observers
+    pub fn step(&mut self, dt: f32) -> CSpaceSnapshot {
+        let results = self.plant_engine.update(dt);
+        self.cspace_engine.plants = self.plant_engine.env.plants.clone();
+        self.cspace_engine.environment = self.plant_engine.env.environment.clone();
+        let snapshot = self.cspace_engine.update(dt);
+        self.emit(SimulationEvent::Step(results));
+        self.emit(SimulationEvent::Snapshot(snapshot.clone()));
+        if self.rate.tick() && self.state == RunState::Running {
+            self.emit(SimulationEvent::Status(self.status()));
         }
+        snapshot
+    }
+
+    fn emit(&mut self, event: SimulationEvent) {
+        let env = &self.plant_engine.env;
+        self.observers.retain_mut(|(_, observer)| observer.notify(env, &event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::simulation::scenario::Scenario;
+    use super::*;
+
+    #[test]
+    fn test_step_notifies_observers() {
+        let (mut runner, _commands) = SimulationRunner::new(SimulationEnv::with_seed(3));
+        let (sender, receiver) = channel();
+        runner.subscribe(Box::new(sender));
+        runner.step(SimulationRunner::DT);
+        assert!(matches!(receiver.try_recv(), Ok(SimulationEvent::Step(_))));
+        assert!(matches!(receiver.try_recv(), Ok(SimulationEvent::Snapshot(_))));
+        assert_eq!(runner.status().step, 1);
+
+        // A dropped receiver unsubscribes
+        drop(receiver);
+        runner.step(SimulationRunner::DT);
+        assert!(runner.observers.is_empty());
+
+        // A detached observer is dropped at once, without waiting for an event
+        let (sender, receiver) = channel();
+        let id = ObserverId::fresh();
+        runner.handle(ControlCommand::Attach(id, Box::new(sender)));
+        runner.handle(ControlCommand::Detach(ObserverId::fresh()));
+        assert_eq!(runner.observers.len(), 1);
+        runner.handle(ControlCommand::Detach(id));
+        assert!(runner.observers.is_empty());
+        assert!(receiver.recv().is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_start_stop_shutdown() {
+        let (runner, commands) = SimulationRunner::new(SimulationEnv::with_seed(3));
+        let handle = runner.spawn();
+        let (sender, receiver) = channel();
+        commands.send(ControlCommand::Subscribe(Box::new(sender))).unwrap();
+        commands.send(ControlCommand::Start).unwrap();
+        let status = |event| match event {
+            SimulationEvent::Status(status) => Some(status),
+            _ => None,
+        };
+        assert!(status(receiver.recv().unwrap()).unwrap().running());
+        assert!(matches!(receiver.recv().unwrap(), SimulationEvent::Step(_)));
+
+        commands.send(ControlCommand::Stop).unwrap();
+        let stopped = receiver.iter().find_map(status).unwrap();
+        assert!(!stopped.running() && stopped.step >= 1);
+
+        // Edits run on the runner thread between steps
+        let (reply, response) = channel();
+        commands.send(ControlCommand::Edit(Box::new(move |env| reply.send(env.step).unwrap()))).unwrap();
+        assert_eq!(response.recv().unwrap(), stopped.step);
+
+        // Mutations are acknowledged to the caller and reported to observers
+        let (reply, response) = channel();
+        let mutation = Mutation::RemovePlant(99);
+        commands.send(ControlCommand::Mutate(mutation.clone(), Some(reply))).unwrap();
+        let result = response.recv().unwrap();
+        assert!(result.is_err());
+        let reported = receiver.iter().find_map(|event| match event {
+            SimulationEvent::Mutation(m, r) => Some((m, r)),
+            _ => None,
+        });
+        assert_eq!(reported, Some((mutation, result)));
+
+        commands.send(ControlCommand::Shutdown).unwrap();
+        handle.join().unwrap();
+        // Nothing stepped after Stop, and the channel closes with the runner
+        assert!(receiver.iter().all(|event| !matches!(event, SimulationEvent::Step(_))));
+    }
+
+    #[test]
+    fn test_pause_step_and_speed() {
+        let (runner, commands) = SimulationRunner::new(SimulationEnv::with_seed(3));
+        let handle = runner.spawn();
+        let (sender, receiver) = channel();
+        commands.send(ControlCommand::Subscribe(Box::new(sender))).unwrap();
+        commands.send(ControlCommand::SetDt(-1.0)).unwrap();
+        commands.send(ControlCommand::SetDt(0.05)).unwrap();
+        commands.send(ControlCommand::Start).unwrap();
+        commands.send(ControlCommand::Step(5)).unwrap();
+        let status = |event| match event {
+            SimulationEvent::Status(status) => Some(status),
+            _ => None,
+        };
+        // Rejected dt, dt, Running, Paused, then the status after the 5 queued steps
+        let reported: Vec<RunnerStatus> = receiver.iter().filter_map(status).take(5).collect();
+        assert_eq!(reported[0].dt, SimulationRunner::DT);
+        assert_eq!(reported[1].dt, 0.05);
+        assert_eq!(reported[3].state, RunState::Paused);
+        let stepped = &reported[4];
+        assert_eq!(stepped.state, RunState::Paused);
+        assert_eq!(stepped.step, reported[3].step + 5);
+        assert!((stepped.time - reported[3].time - 0.25).abs() < 1e-4);
+
+        commands.send(ControlCommand::SetSpeed(Speed::Unlimited)).unwrap();
+        commands.send(ControlCommand::Resume).unwrap();
+        commands.send(ControlCommand::Pause).unwrap();
+        commands.send(ControlCommand::Shutdown).unwrap();
+        handle.join().unwrap();
+        let reported: Vec<RunnerStatus> = receiver.iter().filter_map(status).collect();
+        assert_eq!(reported[0].speed, Speed::Unlimited);
+        assert_eq!(reported[1].state, RunState::Running);
+        assert_eq!(reported.last().unwrap().state, RunState::Paused);
+
+        // A valid but tiny multiplier waits the longest frame instead of overflowing
+        assert_eq!(frame_duration(0.1, Speed::RealTime(f32::MIN_POSITIVE)), MAX_FRAME);
+        // Stepping from Stopped pauses too
+        let (mut runner, _commands) = SimulationRunner::new(SimulationEnv::with_seed(3));
+        runner.handle(ControlCommand::Step(1));
+        assert_eq!(runner.status().state, RunState::Paused);
+    }
+
+    #[test]
+    fn test_reset_restores_loaded_world() {
+        let scenario = Scenario::example("competition").unwrap();
+        let loaded = scenario.build().unwrap();
+        let (mut runner, _commands) = SimulationRunner::new(SimulationEnv::with_seed(3));
+        runner.handle(ControlCommand::Restore(Box::new(Checkpoint { env: loaded.clone(), dt: 0.05 })));
+        runner.step(0.05);
+        runner.step(0.05);
+        runner.handle(ControlCommand::Reset);
+
+        let env = runner.env();
+        assert_eq!((env.step, env.seed), (0, scenario.seed));
+        assert_eq!(env.plants.len(), loaded.plants.len());
+        assert_eq!(env.plants[0].pos, loaded.plants[0].pos);
+        // The RNG starts over too, so the rerun matches the first
+        assert_eq!(env.rng.clone().gen::<u64>(), loaded.rng.clone().gen::<u64>());
+    }
+}